@@ -0,0 +1,35 @@
+//! Compares hashing many small inputs at once with hashing them one by one.
+//!
+//! Run with `cargo bench --bench batch`.
+
+use std::hint::black_box;
+
+use chksum_sha1 as sha1;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// The number of inputs hashed in every iteration.
+const COUNT: usize = 1024;
+
+/// The lengths of the inputs.
+const LENGTHS: [usize; 4] = [16, 64, 256, 1024];
+
+fn hash(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("hash");
+    for length in LENGTHS {
+        let inputs: Vec<Vec<u8>> = (0..COUNT)
+            .map(|index| (0..length).map(|i| ((index + i) * 31 % 251) as u8).collect())
+            .collect();
+
+        group.throughput(Throughput::Bytes((COUNT * length) as u64));
+        group.bench_with_input(BenchmarkId::new("loop", length), &inputs, |bencher, inputs| {
+            bencher.iter(|| black_box(inputs.iter().map(sha1::hash).collect::<Vec<_>>()));
+        });
+        group.bench_with_input(BenchmarkId::new("batch", length), &inputs, |bencher, inputs| {
+            bencher.iter(|| black_box(sha1::batch::hash(inputs)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, hash);
+criterion_main!(benches);
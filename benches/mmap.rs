@@ -0,0 +1,49 @@
+//! Compares hashing a file through memory mapping with the buffered reads.
+//!
+//! Run with `cargo bench --features mmap --bench mmap`.
+
+use std::fs::File;
+use std::hint::black_box;
+use std::io::{Seek, SeekFrom, Write};
+
+use assert_fs::fixture::FileTouch;
+use assert_fs::prelude::PathChild;
+use assert_fs::TempDir;
+use chksum_sha1 as sha1;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// The lengths of the hashed files.
+const LENGTHS: [usize; 3] = [64 * 1024, 16 * 1024 * 1024, 256 * 1024 * 1024];
+
+fn chksum(criterion: &mut Criterion) {
+    let temp_dir = TempDir::new().expect("temporary directory must be created");
+    let mut group = criterion.benchmark_group("chksum");
+    for length in LENGTHS {
+        let child = temp_dir.child(format!("{length}.bin"));
+        child.touch().expect("file must be created");
+        let data: Vec<u8> = (0..length).map(|i| (i * 31 % 251) as u8).collect();
+        File::create(child.path())
+            .and_then(|mut file| file.write_all(&data))
+            .expect("file must be written");
+        let mut file = File::open(child.path()).expect("file must be opened");
+
+        group.throughput(Throughput::Bytes(length as u64));
+        group.bench_with_input(BenchmarkId::new("buffered", length), &length, |bencher, _| {
+            bencher.iter(|| {
+                file.seek(SeekFrom::Start(0)).expect("file must be rewound");
+                black_box(sha1::chksum(&mut file).expect("file must be hashed"))
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("mmap", length), &length, |bencher, _| {
+            bencher.iter(|| {
+                file.seek(SeekFrom::Start(0)).expect("file must be rewound");
+                // SAFETY: the file is owned by the benchmark and isn't modified while it's hashed
+                black_box(unsafe { sha1::mmap::chksum(&file) }.expect("file must be hashed"))
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, chksum);
+criterion_main!(benches);
@@ -0,0 +1,80 @@
+/*
+ * C bindings for the chksum-sha1 crate.
+ *
+ * Every function returning `chksum_sha1_status` reports `CHKSUM_SHA1_OK` on
+ * success and writes its result only then. Digests are written as 20 raw bytes.
+ *
+ * A panic of the Rust code never unwinds into the caller, it's reported as
+ * `CHKSUM_SHA1_ERROR_PANIC` instead.
+ */
+
+#ifndef CHKSUM_SHA1_H
+#define CHKSUM_SHA1_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The length of the digest in bytes. */
+#define CHKSUM_SHA1_DIGEST_LENGTH 20
+
+/* The status of an operation. */
+typedef enum chksum_sha1_status {
+    /* The operation has succeeded. */
+    CHKSUM_SHA1_OK = 0,
+    /* A required pointer is null. */
+    CHKSUM_SHA1_ERROR_NULL_POINTER = 1,
+    /* The path isn't valid on this platform, e.g. isn't UTF-8 on Windows. */
+    CHKSUM_SHA1_ERROR_INVALID_PATH = 2,
+    /* The file or directory doesn't exist. */
+    CHKSUM_SHA1_ERROR_NOT_FOUND = 3,
+    /* The file or directory cannot be accessed. */
+    CHKSUM_SHA1_ERROR_PERMISSION_DENIED = 4,
+    /* Any other I/O error. */
+    CHKSUM_SHA1_ERROR_IO = 5,
+    /* The file is a terminal, which cannot be hashed. */
+    CHKSUM_SHA1_ERROR_IS_TERMINAL = 6,
+    /* The operation has panicked, the hash state involved must only be released. */
+    CHKSUM_SHA1_ERROR_PANIC = 7
+} chksum_sha1_status;
+
+/* An opaque hash state. */
+typedef struct chksum_sha1 chksum_sha1;
+
+/* Creates a new hash state, which must be released with `chksum_sha1_free`, or returns null on panic. */
+chksum_sha1 *chksum_sha1_new(void);
+
+/* Releases the hash state. Does nothing if `hash` is null. */
+void chksum_sha1_free(chksum_sha1 *hash);
+
+/* Updates the hash state with `length` bytes of `data`, which may be null if `length` is 0. */
+chksum_sha1_status chksum_sha1_update(chksum_sha1 *hash, const uint8_t *data, size_t length);
+
+/* Writes the digest of the data hashed so far, the state may be updated further. */
+chksum_sha1_status chksum_sha1_digest(const chksum_sha1 *hash, uint8_t digest[CHKSUM_SHA1_DIGEST_LENGTH]);
+
+/* Resets the hash state to its initial state. */
+chksum_sha1_status chksum_sha1_reset(chksum_sha1 *hash);
+
+/* Writes the digest of `length` bytes of `data`, which may be null if `length` is 0. */
+chksum_sha1_status chksum_sha1_hash(const uint8_t *data, size_t length, uint8_t digest[CHKSUM_SHA1_DIGEST_LENGTH]);
+
+/*
+ * Writes the digest of the file or of all files of the directory at the null-terminated `path`.
+ *
+ * The directories are processed the same way as by the `chksum` function of the crate, so the
+ * digests are the same as computed by Rust code.
+ */
+chksum_sha1_status chksum_sha1_chksum(const char *path, uint8_t digest[CHKSUM_SHA1_DIGEST_LENGTH]);
+
+/* Returns the static, null-terminated description of the status. */
+const char *chksum_sha1_status_message(int status);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CHKSUM_SHA1_H */
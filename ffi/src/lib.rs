@@ -0,0 +1,272 @@
+//! C bindings for the [`chksum-sha1`](https://docs.rs/chksum-sha1) crate.
+//!
+//! The crate is built as a dynamic and a static library exposing the functions declared in the `include/chksum_sha1.h` header, so programs written in C, or in any language able to call C functions like Go with cgo, compute the same digests of files and directories as Rust code.
+//!
+//! # Building
+//!
+//! ```shell
+//! cargo build --release --package chksum-sha1-ffi
+//! ```
+//!
+//! The libraries are placed in the `target/release` directory, named `libchksum_sha1_ffi.so` and `libchksum_sha1_ffi.a` on Linux.
+//! A program linked with the static library must also link the system libraries required by the Rust standard library, e.g. `-lpthread -ldl -lm` on Linux.
+//!
+//! A panic never unwinds into the calling code: the functions catch it and report `CHKSUM_SHA1_ERROR_PANIC` instead.
+//!
+//! # Example
+//!
+//! ```c
+//! #include <stdio.h>
+//!
+//! #include "chksum_sha1.h"
+//!
+//! int main(void) {
+//!     uint8_t digest[CHKSUM_SHA1_DIGEST_LENGTH];
+//!     chksum_sha1_status status = chksum_sha1_chksum("/etc", digest);
+//!     if (status != CHKSUM_SHA1_OK) {
+//!         fprintf(stderr, "%s\n", chksum_sha1_status_message(status));
+//!         return 1;
+//!     }
+//!     for (size_t i = 0; i < CHKSUM_SHA1_DIGEST_LENGTH; i++) {
+//!         printf("%02x", digest[i]);
+//!     }
+//!     printf("\n");
+//!     return 0;
+//! }
+//! ```
+
+use std::ffi::{c_char, c_int, CStr};
+use std::io::ErrorKind;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::{ptr, slice};
+
+use chksum_sha1::{Digest, Error, DIGEST_LENGTH_BYTES, SHA1};
+
+/// The status of an operation, mirroring `chksum_sha1_status` of the header.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The operation has succeeded.
+    Ok = 0,
+    /// A required pointer is null.
+    NullPointer = 1,
+    /// The path isn't valid on this platform.
+    InvalidPath = 2,
+    /// The file or directory doesn't exist.
+    NotFound = 3,
+    /// The file or directory cannot be accessed.
+    PermissionDenied = 4,
+    /// Any other I/O error.
+    Io = 5,
+    /// The file is a terminal.
+    IsTerminal = 6,
+    /// The operation has panicked.
+    Panic = 7,
+}
+
+impl Status {
+    /// All statuses, in the order of their values.
+    const ALL: [Self; 8] = [
+        Self::Ok,
+        Self::NullPointer,
+        Self::InvalidPath,
+        Self::NotFound,
+        Self::PermissionDenied,
+        Self::Io,
+        Self::IsTerminal,
+        Self::Panic,
+    ];
+
+    /// Returns the null-terminated description of the status.
+    fn message(self) -> &'static [u8] {
+        match self {
+            Self::Ok => b"Success\0",
+            Self::NullPointer => b"Null pointer\0",
+            Self::InvalidPath => b"Invalid path\0",
+            Self::NotFound => b"File or directory not found\0",
+            Self::PermissionDenied => b"Permission denied\0",
+            Self::Io => b"I/O error\0",
+            Self::IsTerminal => b"Cannot process terminal input\0",
+            Self::Panic => b"Unexpected panic\0",
+        }
+    }
+}
+
+impl From<&Error> for Status {
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::IsTerminal => Self::IsTerminal,
+            Error::Io(error) => {
+                match error.kind() {
+                    ErrorKind::NotFound => Self::NotFound,
+                    ErrorKind::PermissionDenied => Self::PermissionDenied,
+                    _ => Self::Io,
+                }
+            },
+        }
+    }
+}
+
+/// An opaque hash state, `chksum_sha1` of the header.
+#[derive(Debug)]
+pub struct Hash(SHA1);
+
+/// Creates a new hash state, which must be released with [`chksum_sha1_free`], or returns null on panic.
+#[no_mangle]
+pub extern "C" fn chksum_sha1_new() -> *mut Hash {
+    guard(ptr::null_mut(), || Box::into_raw(Box::new(Hash(SHA1::new()))))
+}
+
+/// Releases the hash state.
+///
+/// # Safety
+///
+/// The `hash` must be null or returned by [`chksum_sha1_new`] and not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn chksum_sha1_free(hash: *mut Hash) {
+    guard((), || {
+        if !hash.is_null() {
+            drop(Box::from_raw(hash));
+        }
+    });
+}
+
+/// Updates the hash state with the data.
+///
+/// # Safety
+///
+/// The `hash` must be null or a valid hash state, and `data` must be valid for reads of `length` bytes unless `length` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn chksum_sha1_update(hash: *mut Hash, data: *const u8, length: usize) -> Status {
+    guard(Status::Panic, || {
+        let Some(Hash(hash)) = hash.as_mut() else {
+            return Status::NullPointer;
+        };
+        let Some(data) = bytes(data, length) else {
+            return Status::NullPointer;
+        };
+        hash.update(data);
+        Status::Ok
+    })
+}
+
+/// Writes the digest of the data hashed so far.
+///
+/// # Safety
+///
+/// The `hash` must be null or a valid hash state, and `digest` must be null or valid for writes of 20 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn chksum_sha1_digest(hash: *const Hash, digest: *mut u8) -> Status {
+    guard(Status::Panic, || {
+        let Some(Hash(hash)) = hash.as_ref() else {
+            return Status::NullPointer;
+        };
+        write(hash.digest(), digest)
+    })
+}
+
+/// Resets the hash state to its initial state.
+///
+/// # Safety
+///
+/// The `hash` must be null or a valid hash state.
+#[no_mangle]
+pub unsafe extern "C" fn chksum_sha1_reset(hash: *mut Hash) -> Status {
+    guard(Status::Panic, || {
+        let Some(Hash(hash)) = hash.as_mut() else {
+            return Status::NullPointer;
+        };
+        hash.reset();
+        Status::Ok
+    })
+}
+
+/// Writes the digest of the data.
+///
+/// # Safety
+///
+/// The `data` must be valid for reads of `length` bytes unless `length` is 0, and `digest` must be null or valid for writes of 20 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn chksum_sha1_hash(data: *const u8, length: usize, digest: *mut u8) -> Status {
+    guard(Status::Panic, || {
+        let Some(data) = bytes(data, length) else {
+            return Status::NullPointer;
+        };
+        write(chksum_sha1::hash(data), digest)
+    })
+}
+
+/// Writes the digest of the file or of all files of the directory.
+///
+/// # Safety
+///
+/// The `path` must be null or a valid null-terminated string, and `digest` must be null or valid for writes of 20 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn chksum_sha1_chksum(path: *const c_char, digest: *mut u8) -> Status {
+    guard(Status::Panic, || {
+        if path.is_null() || digest.is_null() {
+            return Status::NullPointer;
+        }
+        let Some(path) = to_path(CStr::from_ptr(path)) else {
+            return Status::InvalidPath;
+        };
+        match chksum_sha1::chksum(path) {
+            Ok(computed) => write(computed, digest),
+            Err(error) => Status::from(&error),
+        }
+    })
+}
+
+/// Returns the static, null-terminated description of the status, or of an unknown status.
+#[no_mangle]
+pub extern "C" fn chksum_sha1_status_message(status: c_int) -> *const c_char {
+    guard(Status::Panic.message(), || {
+        usize::try_from(status)
+            .ok()
+            .and_then(|status| Status::ALL.get(status))
+            .map_or(&b"Unknown status\0"[..], |status| status.message())
+    })
+    .as_ptr()
+    .cast()
+}
+
+/// Calls the function, returning `fallback` instead of unwinding if it panics.
+fn guard<T>(fallback: T, function: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(function)).unwrap_or(fallback)
+}
+
+/// Returns the slice of the data, or `None` if the pointer is null while the length isn't 0.
+unsafe fn bytes<'a>(data: *const u8, length: usize) -> Option<&'a [u8]> {
+    if length == 0 {
+        Some(&[])
+    } else if data.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(data, length))
+    }
+}
+
+/// Writes the digest bytes to the output.
+unsafe fn write(digest: Digest, output: *mut u8) -> Status {
+    if output.is_null() {
+        return Status::NullPointer;
+    }
+    ptr::copy_nonoverlapping(digest.as_bytes().as_ptr(), output, DIGEST_LENGTH_BYTES);
+    Status::Ok
+}
+
+/// Converts the C string to a path, taking the bytes as they are on Unix and requiring UTF-8 elsewhere.
+fn to_path(path: &CStr) -> Option<PathBuf> {
+    #[cfg(unix)]
+    {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        Some(PathBuf::from(OsStr::from_bytes(path.to_bytes())))
+    }
+    #[cfg(not(unix))]
+    {
+        path.to_str().ok().map(PathBuf::from)
+    }
+}
@@ -0,0 +1,56 @@
+#![cfg(unix)]
+
+use std::env;
+use std::io::Error as IoError;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteStr, PathChild, PathCreateDir};
+use assert_fs::TempDir;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+/// Returns the static library built by Cargo, which is placed in the `deps` directory next to the test executable.
+fn static_library() -> Result<PathBuf, Error> {
+    let executable = env::current_exe()?;
+    let deps_dir = executable
+        .parent()
+        .expect("test executable must have a parent directory");
+    Ok(deps_dir.join("libchksum_sha1_ffi.a"))
+}
+
+#[test]
+fn c() -> Result<(), Error> {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let temp_dir = TempDir::new()?;
+    let executable = temp_dir.child("test");
+
+    let compiler = env::var_os("CC").unwrap_or_else(|| "cc".into());
+    let status = Command::new(compiler)
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg(manifest_dir.join("tests/c/test.c"))
+        .arg("-o")
+        .arg(executable.path())
+        .arg(static_library()?)
+        .args(["-lpthread", "-ldl", "-lm"])
+        .status()?;
+    assert!(status.success(), "compilation of the C test failed");
+
+    let fixture = temp_dir.child("fixture");
+    fixture.child("b").create_dir_all()?;
+    fixture.child("a.txt").write_str("example")?;
+    fixture.child("b/c.txt").write_str(" data")?;
+
+    let status = Command::new(executable.path()).arg(fixture.path()).status()?;
+    assert!(status.success(), "C test failed");
+
+    Ok(())
+}
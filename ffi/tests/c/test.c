@@ -0,0 +1,108 @@
+/*
+ * Exercises the C interface, given a directory with the `a.txt` file containing "example" and
+ * the `b/c.txt` file containing " data".
+ */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "chksum_sha1.h"
+
+/* sha1("example data") */
+static const char *EXAMPLE_DATA = "9fc42adac31303d68b444e6129f13f6093a0e045";
+/* sha1("") */
+static const char *EMPTY = "da39a3ee5e6b4b0d3255bfef95601890afd80709";
+
+static int failures = 0;
+
+#define CHECK(condition)                                                     \
+    do {                                                                     \
+        if (!(condition)) {                                                  \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, \
+                    #condition);                                             \
+            failures++;                                                      \
+        }                                                                    \
+    } while (0)
+
+static int equals(const uint8_t digest[CHKSUM_SHA1_DIGEST_LENGTH], const char *expected) {
+    char hex[2 * CHKSUM_SHA1_DIGEST_LENGTH + 1];
+    for (size_t i = 0; i < CHKSUM_SHA1_DIGEST_LENGTH; i++) {
+        snprintf(&hex[2 * i], 3, "%02x", digest[i]);
+    }
+    return strcmp(hex, expected) == 0;
+}
+
+static void test_hash(void) {
+    uint8_t digest[CHKSUM_SHA1_DIGEST_LENGTH];
+    const char *data = "example data";
+
+    CHECK(chksum_sha1_hash((const uint8_t *)data, strlen(data), digest) == CHKSUM_SHA1_OK);
+    CHECK(equals(digest, EXAMPLE_DATA));
+    CHECK(chksum_sha1_hash(NULL, 0, digest) == CHKSUM_SHA1_OK);
+    CHECK(equals(digest, EMPTY));
+    CHECK(chksum_sha1_hash(NULL, 1, digest) == CHKSUM_SHA1_ERROR_NULL_POINTER);
+    CHECK(chksum_sha1_hash((const uint8_t *)data, strlen(data), NULL) == CHKSUM_SHA1_ERROR_NULL_POINTER);
+}
+
+static void test_state(void) {
+    uint8_t digest[CHKSUM_SHA1_DIGEST_LENGTH];
+    chksum_sha1 *hash = chksum_sha1_new();
+    CHECK(hash != NULL);
+
+    CHECK(chksum_sha1_digest(hash, digest) == CHKSUM_SHA1_OK);
+    CHECK(equals(digest, EMPTY));
+    CHECK(chksum_sha1_update(hash, (const uint8_t *)"example", 7) == CHKSUM_SHA1_OK);
+    CHECK(chksum_sha1_update(hash, (const uint8_t *)" data", 5) == CHKSUM_SHA1_OK);
+    CHECK(chksum_sha1_digest(hash, digest) == CHKSUM_SHA1_OK);
+    CHECK(equals(digest, EXAMPLE_DATA));
+    CHECK(chksum_sha1_reset(hash) == CHKSUM_SHA1_OK);
+    CHECK(chksum_sha1_digest(hash, digest) == CHKSUM_SHA1_OK);
+    CHECK(equals(digest, EMPTY));
+
+    CHECK(chksum_sha1_update(NULL, NULL, 0) == CHKSUM_SHA1_ERROR_NULL_POINTER);
+    CHECK(chksum_sha1_digest(NULL, digest) == CHKSUM_SHA1_ERROR_NULL_POINTER);
+    CHECK(chksum_sha1_reset(NULL) == CHKSUM_SHA1_ERROR_NULL_POINTER);
+
+    chksum_sha1_free(hash);
+    chksum_sha1_free(NULL);
+}
+
+static void test_chksum(const char *directory) {
+    uint8_t digest[CHKSUM_SHA1_DIGEST_LENGTH];
+    char path[4096];
+
+    CHECK(chksum_sha1_chksum(directory, digest) == CHKSUM_SHA1_OK);
+    CHECK(equals(digest, EXAMPLE_DATA));
+
+    snprintf(path, sizeof(path), "%s/b/c.txt", directory);
+    CHECK(chksum_sha1_chksum(path, digest) == CHKSUM_SHA1_OK);
+    uint8_t expected[CHKSUM_SHA1_DIGEST_LENGTH];
+    CHECK(chksum_sha1_hash((const uint8_t *)" data", 5, expected) == CHKSUM_SHA1_OK);
+    CHECK(memcmp(digest, expected, CHKSUM_SHA1_DIGEST_LENGTH) == 0);
+
+    snprintf(path, sizeof(path), "%s/missing.txt", directory);
+    CHECK(chksum_sha1_chksum(path, digest) == CHKSUM_SHA1_ERROR_NOT_FOUND);
+    CHECK(chksum_sha1_chksum(NULL, digest) == CHKSUM_SHA1_ERROR_NULL_POINTER);
+}
+
+static void test_status_message(void) {
+    CHECK(strcmp(chksum_sha1_status_message(CHKSUM_SHA1_OK), "Success") == 0);
+    CHECK(strcmp(chksum_sha1_status_message(CHKSUM_SHA1_ERROR_NOT_FOUND), "File or directory not found") == 0);
+    CHECK(strcmp(chksum_sha1_status_message(CHKSUM_SHA1_ERROR_PANIC), "Unexpected panic") == 0);
+    CHECK(strcmp(chksum_sha1_status_message(-1), "Unknown status") == 0);
+    CHECK(strcmp(chksum_sha1_status_message(100), "Unknown status") == 0);
+}
+
+int main(int argc, char **argv) {
+    if (argc != 2) {
+        fprintf(stderr, "usage: %s DIRECTORY\n", argv[0]);
+        return 2;
+    }
+
+    test_hash();
+    test_state();
+    test_chksum(argv[1]);
+    test_status_message();
+
+    return failures == 0 ? 0 : 1;
+}
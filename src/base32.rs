@@ -0,0 +1,54 @@
+//! Base32 encoding without padding, as defined in [RFC 4648](https://www.rfc-editor.org/rfc/rfc4648#section-6).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// The lowercase alphabet.
+pub(crate) const LOWER: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// The uppercase alphabet.
+pub(crate) const UPPER: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Encodes the data with the given alphabet.
+pub(crate) fn encode(data: &[u8], alphabet: &[u8; 32]) -> String {
+    let mut encoded = String::with_capacity((data.len() * 8).div_ceil(5));
+    let mut bits = 0u16;
+    let mut length = 0;
+    for &byte in data {
+        bits = bits << 8 | u16::from(byte);
+        length += 8;
+        while length >= 5 {
+            length -= 5;
+            encoded.push(char::from(alphabet[usize::from((bits >> length) & 0x1F)]));
+        }
+    }
+    if length > 0 {
+        encoded.push(char::from(alphabet[usize::from((bits << (5 - length)) & 0x1F)]));
+    }
+    encoded
+}
+
+/// Decodes the text in the given alphabet, returning `None` if it's invalid.
+pub(crate) fn decode(text: &str, alphabet: &[u8; 32]) -> Option<Vec<u8>> {
+    // the lengths of 1, 3 and 6 characters modulo 8 cannot hold whole bytes
+    if matches!(text.len() % 8, 1 | 3 | 6) {
+        return None;
+    }
+    let mut decoded = Vec::with_capacity(text.len() * 5 / 8);
+    let mut bits = 0u16;
+    let mut length = 0;
+    for x in text.bytes() {
+        let digit = alphabet.iter().position(|&y| y == x)?;
+        bits = bits << 5 | digit as u16;
+        length += 5;
+        if length >= 8 {
+            length -= 8;
+            decoded.push((bits >> length) as u8);
+        }
+    }
+    // the bits not covered by the decoded bytes must be zero
+    if bits & ((1 << length) - 1) != 0 {
+        return None;
+    }
+    Some(decoded)
+}
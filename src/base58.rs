@@ -0,0 +1,53 @@
+//! Base58 encoding with the alphabet of Bitcoin, as defined in the [draft](https://datatracker.ietf.org/doc/html/draft-msporny-base58-03).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// The alphabet of Bitcoin.
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Encodes the data.
+pub(crate) fn encode(data: &[u8]) -> String {
+    // the leading zero bytes are encoded as the leading zero digits
+    let zeros = data.iter().take_while(|&&byte| byte == 0).count();
+    // the digits in the little-endian order
+    let mut digits = Vec::with_capacity(data.len() * 138 / 100 + 1);
+    for &byte in &data[zeros..] {
+        let mut carry = u32::from(byte);
+        for digit in &mut digits {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = core::iter::repeat(ALPHABET[0]).take(zeros);
+    let digits = digits.into_iter().rev().map(|digit| ALPHABET[usize::from(digit)]);
+    zeros.chain(digits).map(char::from).collect()
+}
+
+/// Decodes the text, returning `None` if it's invalid.
+pub(crate) fn decode(text: &str) -> Option<Vec<u8>> {
+    let text = text.as_bytes();
+    let zeros = text.iter().take_while(|&&x| x == ALPHABET[0]).count();
+    // the bytes in the little-endian order
+    let mut bytes = Vec::with_capacity(text.len() * 733 / 1000 + 1);
+    for x in &text[zeros..] {
+        let mut carry = ALPHABET.iter().position(|y| y == x)? as u32;
+        for byte in &mut bytes {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    bytes.extend(core::iter::repeat(0).take(zeros));
+    bytes.reverse();
+    Some(bytes)
+}
@@ -0,0 +1,156 @@
+//! Base64 encodings, as defined in [RFC 4648](https://www.rfc-editor.org/rfc/rfc4648#section-4).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+const PADDING: u8 = b'=';
+
+/// The standard alphabet with padding.
+pub(crate) const STANDARD: Engine = Engine::new(
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+    true,
+);
+
+/// The standard alphabet without padding.
+pub(crate) const STANDARD_NO_PAD: Engine = STANDARD.without_padding();
+
+/// The URL and filename safe alphabet with padding.
+pub(crate) const URL_SAFE: Engine = Engine::new(
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+    true,
+);
+
+/// The URL and filename safe alphabet without padding.
+pub(crate) const URL_SAFE_NO_PAD: Engine = URL_SAFE.without_padding();
+
+/// Encodes the data with the standard alphabet, padding the output to a multiple of 4 characters.
+pub(crate) fn encode(data: impl AsRef<[u8]>) -> String {
+    STANDARD.encode(data)
+}
+
+/// Decodes the padded text with the standard alphabet, returning `None` if it isn't canonical Base64.
+pub(crate) fn decode(text: impl AsRef<[u8]>) -> Option<Vec<u8>> {
+    STANDARD.decode(text)
+}
+
+/// A variant of Base64 with the given alphabet and padding.
+pub(crate) struct Engine {
+    alphabet: &'static [u8; 64],
+    padding: bool,
+}
+
+impl Engine {
+    const fn new(alphabet: &'static [u8; 64], padding: bool) -> Self {
+        Self { alphabet, padding }
+    }
+
+    const fn without_padding(self) -> Self {
+        Self::new(self.alphabet, false)
+    }
+
+    /// Encodes the data.
+    pub(crate) fn encode(&self, data: impl AsRef<[u8]>) -> String {
+        let data = data.as_ref();
+        let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+        for chunk in data.chunks(3) {
+            let mut block = [0u8; 3];
+            block[..chunk.len()].copy_from_slice(chunk);
+            let bits = u32::from(block[0]) << 16 | u32::from(block[1]) << 8 | u32::from(block[2]);
+            for index in 0..4 {
+                if index <= chunk.len() {
+                    let digit = (bits >> (18 - 6 * index)) & 0x3F;
+                    encoded.push(char::from(self.alphabet[digit as usize]));
+                } else if self.padding {
+                    encoded.push(char::from(PADDING));
+                }
+            }
+        }
+        encoded
+    }
+
+    /// Decodes the text, returning `None` if it isn't canonical in this variant.
+    pub(crate) fn decode(&self, text: impl AsRef<[u8]>) -> Option<Vec<u8>> {
+        let text = text.as_ref();
+        let text = if self.padding {
+            if text.len() % 4 != 0 {
+                return None;
+            }
+            let padding = text.iter().rev().take_while(|&&x| x == PADDING).count();
+            if padding > 2 {
+                return None;
+            }
+            &text[..text.len() - padding]
+        } else {
+            text
+        };
+        // a single character of the last block cannot hold a whole byte
+        if text.len() % 4 == 1 {
+            return None;
+        }
+
+        let mut decoded = Vec::with_capacity(text.len() * 3 / 4);
+        for block in text.chunks(4) {
+            let mut bits = 0u32;
+            for &x in block {
+                let digit = self.alphabet.iter().position(|&y| y == x)?;
+                bits = bits << 6 | digit as u32;
+            }
+            bits <<= 6 * (4 - block.len());
+            let bytes = bits.to_be_bytes();
+            let length = block.len() - 1;
+            // the bits not covered by the decoded bytes must be zero
+            if bits & (0xFF_FF_FF >> (8 * length)) != 0 {
+                return None;
+            }
+            decoded.extend_from_slice(&bytes[1..=length]);
+        }
+        Some(decoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc4648() {
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (data, encoded) in vectors {
+            assert_eq!(encode(data), encoded);
+            assert_eq!(decode(encoded).as_deref(), Some(data.as_bytes()));
+
+            let encoded = encoded.trim_end_matches('=');
+            assert_eq!(STANDARD_NO_PAD.encode(data), encoded);
+            assert_eq!(STANDARD_NO_PAD.decode(encoded).as_deref(), Some(data.as_bytes()));
+        }
+    }
+
+    #[test]
+    fn url_safe() {
+        let data = [0xFB, 0xFF, 0xBF];
+        assert_eq!(STANDARD.encode(data), "+/+/");
+        assert_eq!(URL_SAFE.encode(data), "-_-_");
+        assert_eq!(URL_SAFE.encode([0xFB]), "-w==");
+        assert_eq!(URL_SAFE_NO_PAD.encode([0xFB]), "-w");
+        assert_eq!(URL_SAFE.decode("-_-_").as_deref(), Some(&data[..]));
+        assert_eq!(URL_SAFE.decode("+/+/"), None);
+    }
+
+    #[test]
+    fn invalid() {
+        for text in ["Zg", "Zg=", "Z===", "Zh==", "Zg==Zg==", "Zm9v!A==", "Zm=v"] {
+            assert_eq!(decode(text), None, "{text}");
+        }
+        for text in ["Z", "Zg=", "Zh", "Zm9vZ"] {
+            assert_eq!(STANDARD_NO_PAD.decode(text), None, "{text}");
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! This module provides computation of digests of many independent inputs at once.
+//!
+//! With the `acceleration` Cargo feature enabled, on x86 CPUs with AVX2 instructions but without SHA extensions, the inputs are distributed between 8 lanes which are processed together with SIMD instructions.
+//! When a lane finishes its input, it immediately continues with the next one, so inputs of different lengths can be mixed freely.
+//!
+//! Everywhere else the functions of this module are only a convenience loop over [`hash`](crate::hash()), with no speedup over calling it for every input.
+//! The `batch` benchmark (`cargo bench --bench batch`) shows why: the lanes computed without SIMD instructions reach about half the throughput of the single-input backend, and the AVX2 lanes are about 3 times faster than the portable backend but still slower than SHA extensions.
+//!
+//! The digests are always returned in the order of the inputs and are the same as computed by [`hash`](crate::hash()).
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_sha1 as sha1;
+//!
+//! let inputs = ["first", "second", "third"];
+//! let digests = sha1::batch::hash(&inputs);
+//! assert_eq!(digests.len(), 3);
+//! assert_eq!(digests[1], sha1::hash("second"));
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::compress::{self, BLOCK_LENGTH_BYTES, LANES};
+use crate::state::{self, H};
+use crate::{Digest, SHA1};
+
+/// Computes the digests of all inputs.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1 as sha1;
+///
+/// let blobs = vec![b"example".to_vec(), b"data".to_vec()];
+/// let digests = sha1::batch::hash(&blobs);
+/// assert_eq!(
+///     digests[0].to_hex_lowercase(),
+///     "c3499c2729730a7f807efb8676a92dcb6f8a3f8f"
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn hash<T>(inputs: &[T]) -> Vec<Digest>
+where
+    T: AsRef<[u8]>,
+{
+    let mut digests = alloc::vec![Digest::new([0u8; crate::DIGEST_LENGTH_BYTES]); inputs.len()];
+    hash_into(inputs, &mut digests);
+    digests
+}
+
+/// Computes the digests of all inputs into the given slice, without allocating memory.
+///
+/// # Panics
+///
+/// Panics if the lengths of `inputs` and `digests` differ.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1 as sha1;
+///
+/// let inputs: [&[u8]; 2] = [b"example", b"data"];
+/// let mut digests = [sha1::Digest::new([0; 20]); 2];
+/// sha1::batch::hash_into(&inputs, &mut digests);
+/// assert_eq!(digests[0], sha1::hash(b"example"));
+/// ```
+pub fn hash_into<T>(inputs: &[T], digests: &mut [Digest])
+where
+    T: AsRef<[u8]>,
+{
+    assert_eq!(
+        inputs.len(),
+        digests.len(),
+        "inputs and digests must have the same length"
+    );
+
+    if compress::is_lanes_preferred() {
+        hash_lanes(inputs, digests);
+    } else {
+        for (input, digest) in inputs.iter().zip(digests) {
+            *digest = SHA1::hash(input);
+        }
+    }
+}
+
+/// Computes the digests of all inputs, interleaving the blocks of up to [`LANES`] inputs.
+fn hash_lanes<T>(inputs: &[T], digests: &mut [Digest])
+where
+    T: AsRef<[u8]>,
+{
+    let mut lanes: [Option<Lane<'_>>; LANES] = Default::default();
+    let mut states = [[0u32; LANES]; 5];
+    let mut inputs = inputs.iter().map(AsRef::as_ref).enumerate();
+    let mut active = 0;
+    loop {
+        for (lane_index, lane) in lanes.iter_mut().enumerate() {
+            if lane.is_none() {
+                if let Some((index, data)) = inputs.next() {
+                    *lane = Some(Lane::new(index, data));
+                    for (state, value) in states.iter_mut().zip(H) {
+                        state[lane_index] = value;
+                    }
+                    active += 1;
+                }
+            }
+        }
+        match active {
+            0 => break,
+            // a single remaining input is finished faster with the single-block backend
+            1 if inputs.len() == 0 => {
+                let (lane_index, lane) = lanes
+                    .iter_mut()
+                    .enumerate()
+                    .find_map(|(lane_index, lane)| lane.take().map(|lane| (lane_index, lane)))
+                    .expect("one lane must be active");
+                let mut state = states.map(|state| state[lane_index]);
+                compress::compress(&mut state, lane.data);
+                compress::compress(&mut state, &lane.tail[lane.tail_offset..lane.tail_length]);
+                digests[lane.index] = Digest::new(state::encode(state));
+                break;
+            },
+            _ => {},
+        }
+
+        let mut blocks = [[0u8; BLOCK_LENGTH_BYTES]; LANES];
+        for (block, lane) in blocks.iter_mut().zip(&mut lanes) {
+            if let Some(lane) = lane {
+                block.copy_from_slice(lane.next_block());
+            }
+        }
+        compress::compress_lanes(&mut states, &blocks);
+
+        for (lane_index, lane) in lanes.iter_mut().enumerate() {
+            if lane.as_ref().is_some_and(Lane::is_finished) {
+                let Lane { index, .. } = lane.take().expect("lane must be active");
+                digests[index] = Digest::new(state::encode(states.map(|state| state[lane_index])));
+                active -= 1;
+            }
+        }
+    }
+}
+
+/// A single input assigned to a lane.
+struct Lane<'a> {
+    /// The position of the input, and thus the digest.
+    index: usize,
+    /// The complete blocks of the input which haven't been processed yet.
+    data: &'a [u8],
+    /// The final blocks with the tail of the input and the padding.
+    tail: [u8; 2 * BLOCK_LENGTH_BYTES],
+    tail_length: usize,
+    tail_offset: usize,
+}
+
+impl<'a> Lane<'a> {
+    fn new(index: usize, data: &'a [u8]) -> Self {
+        let (data, unprocessed) = data.split_at(data.len() - data.len() % BLOCK_LENGTH_BYTES);
+        let length = (data.len() + unprocessed.len()) as u64;
+        let (tail, tail_length) = state::pad(unprocessed, length);
+        Self {
+            index,
+            data,
+            tail,
+            tail_length,
+            tail_offset: 0,
+        }
+    }
+
+    /// Returns the next block to process.
+    fn next_block(&mut self) -> &[u8] {
+        if self.data.is_empty() {
+            let block = &self.tail[self.tail_offset..self.tail_offset + BLOCK_LENGTH_BYTES];
+            self.tail_offset += BLOCK_LENGTH_BYTES;
+            block
+        } else {
+            let (block, data) = self.data.split_at(BLOCK_LENGTH_BYTES);
+            self.data = data;
+            block
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.data.is_empty() && self.tail_offset == self.tail_length
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lanes() {
+        let mut data = [0u8; 1000];
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = (i * 31 % 251) as u8;
+        }
+        // lengths around the block and padding boundaries, in an order which keeps the lanes uneven
+        let mut inputs = [&data[..0]; 40];
+        for (i, input) in inputs.iter_mut().enumerate() {
+            *input = &data[..(i * 173) % 1000];
+        }
+        inputs[1] = &data[..55];
+        inputs[2] = &data[..56];
+        inputs[3] = &data[..64];
+        inputs[4] = &data[..119];
+        inputs[5] = &data[..120];
+
+        for count in [0, 1, 2, LANES, LANES + 1, inputs.len()] {
+            let mut digests = [Digest::new([0u8; crate::DIGEST_LENGTH_BYTES]); 40];
+            hash_lanes(&inputs[..count], &mut digests[..count]);
+            for (input, digest) in inputs[..count].iter().zip(&digests[..count]) {
+                assert_eq!(*digest, SHA1::hash(input));
+            }
+        }
+    }
+}
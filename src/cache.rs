@@ -0,0 +1,257 @@
+//! This module provides a persistent cache of file digests.
+//!
+//! The [`Cache`] remembers the digest of every file it has computed together with the identity of the file (device and inode numbers), its size and its modification time.
+//! As long as none of them changes, the cached digest is returned without reading the file again.
+//! The cache is stored in a file between runs, e.g. in a CI cache directory.
+//!
+//! # Racy Files
+//!
+//! A file modified within the granularity of the filesystem timestamps after its digest has been computed keeps the same modification time, so its cached digest can no longer be trusted.
+//! To prevent returning such stale digests, the cache ignores all entries whose modification time isn't older than the moment the digest was known to be valid, minus the [safety window](Cache::set_safety_window).
+//! For entries loaded from the cache file, this moment is the time the file was written, so files modified shortly before saving the cache are always hashed again.
+//!
+//! # Enabling
+//!
+//! This module is optional and can be enabled using the `cache` Cargo feature.
+//! It's available only on Unix platforms, which provide the device and inode numbers.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! # use chksum_sha1::Result;
+//! use chksum_sha1::cache::Cache;
+//!
+//! # fn wrapper(cache: &Path, paths: &[&Path]) -> Result<()> {
+//! let mut cache = Cache::open(cache)?;
+//! for path in paths {
+//!     let digest = cache.chksum(path)?;
+//!     println!("{digest}  {}", path.display());
+//! }
+//! cache.save()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fs::{self, File, Metadata};
+use std::io::{self, BufRead, BufReader, BufWriter, ErrorKind, Write};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{Digest, Result};
+
+/// The first line of the cache file, which identifies its format.
+const HEADER: &str = "chksum-sha1 cache 1";
+
+/// The default safety window, which covers the timestamp granularity of common filesystems.
+const SAFETY_WINDOW: Duration = Duration::from_secs(2);
+
+/// The identity of a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Key {
+    device: u64,
+    inode: u64,
+}
+
+/// The cached digest of a file.
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    size: u64,
+    /// The modification time in nanoseconds since the Unix epoch.
+    modified: i64,
+    digest: Digest,
+    /// The time in nanoseconds since the Unix epoch at which the digest was known to match the file.
+    verified: i64,
+}
+
+/// A persistent cache of file digests keyed on the file identity, size and modification time.
+#[derive(Clone, Debug)]
+pub struct Cache {
+    path: PathBuf,
+    entries: HashMap<Key, Entry>,
+    safety_window: Option<Duration>,
+}
+
+impl Cache {
+    /// Loads the cache from the file, or creates an empty one if the file doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or isn't a valid cache file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let entries = match File::open(&path) {
+            Ok(file) => load(file)?,
+            Err(error) if error.kind() == ErrorKind::NotFound => HashMap::new(),
+            Err(error) => return Err(error.into()),
+        };
+        let safety_window = Some(SAFETY_WINDOW);
+        Ok(Self {
+            path,
+            entries,
+            safety_window,
+        })
+    }
+
+    /// Sets the safety window for racy files, or disables the check with `None`.
+    ///
+    /// The default window is 2 seconds.
+    pub fn set_safety_window(&mut self, safety_window: Option<Duration>) {
+        self.safety_window = safety_window;
+    }
+
+    /// Returns the number of cached digests.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no cached digests.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Computes the hash of the path, returning the cached digest if the file hasn't changed.
+    ///
+    /// Only regular files are cached, everything else is processed just like by [`chksum`](crate::chksum).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::path::Path;
+    /// # use chksum_sha1::Result;
+    /// use chksum_sha1::cache::Cache;
+    ///
+    /// # fn wrapper(path: &Path) -> Result<()> {
+    /// let mut cache = Cache::open("target/sha1.cache")?;
+    /// let first = cache.chksum(path)?;
+    /// let second = cache.chksum(path)?;
+    /// assert_eq!(first, second);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn chksum(&mut self, path: impl AsRef<Path>) -> Result<Digest> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        let metadata = file.metadata()?;
+        if !metadata.is_file() {
+            return crate::chksum(path);
+        }
+
+        let key = Key {
+            device: metadata.dev(),
+            inode: metadata.ino(),
+        };
+        if let Some(entry) = self.entries.get(&key) {
+            if entry.size == metadata.size() && entry.modified == modified(&metadata) && self.is_trusted(entry) {
+                return Ok(entry.digest);
+            }
+        }
+
+        // the digest is known to be valid only for the data read after this moment
+        let verified = now();
+        let digest = crate::chksum(&file)?;
+        let entry = Entry {
+            size: metadata.size(),
+            modified: modified(&metadata),
+            digest,
+            verified,
+        };
+        self.entries.insert(key, entry);
+        Ok(digest)
+    }
+
+    /// Writes the cache to its file.
+    ///
+    /// The entries of racy files are omitted, and the file is replaced atomically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self) -> Result<()> {
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        let temporary = PathBuf::from(temporary);
+
+        let mut writer = BufWriter::new(File::create(&temporary)?);
+        writeln!(writer, "{HEADER}")?;
+        writeln!(writer, "{}", now())?;
+        for (key, entry) in &self.entries {
+            if self.is_trusted(entry) {
+                let Key { device, inode } = key;
+                let Entry {
+                    size, modified, digest, ..
+                } = entry;
+                writeln!(writer, "{device} {inode} {size} {modified} {digest:x}")?;
+            }
+        }
+        writer
+            .into_inner()
+            .map_err(io::IntoInnerError::into_error)?
+            .sync_all()?;
+        fs::rename(&temporary, &self.path)?;
+        Ok(())
+    }
+
+    /// Returns `true` if the file couldn't have been modified without changing its modification time since the digest was verified.
+    fn is_trusted(&self, entry: &Entry) -> bool {
+        let Some(safety_window) = self.safety_window else {
+            return true;
+        };
+        let safety_window = i64::try_from(safety_window.as_nanos()).unwrap_or(i64::MAX);
+        entry.modified < entry.verified.saturating_sub(safety_window)
+    }
+}
+
+/// Parses the entries of the cache file, all of them verified at the time the file was written.
+fn load(file: File) -> io::Result<HashMap<Key, Entry>> {
+    fn invalid() -> io::Error {
+        io::Error::new(ErrorKind::InvalidData, "invalid cache file")
+    }
+
+    let mut lines = BufReader::new(file).lines();
+    if lines.next().transpose()?.as_deref() != Some(HEADER) {
+        return Err(invalid());
+    }
+    let verified = lines.next().transpose()?.ok_or_else(invalid)?;
+    let verified = verified.parse().map_err(|_| invalid())?;
+
+    let mut entries = HashMap::new();
+    for line in lines {
+        let line = line?;
+        let mut fields = line.split(' ');
+        let mut field = || fields.next().ok_or_else(invalid);
+        let key = Key {
+            device: field()?.parse().map_err(|_| invalid())?,
+            inode: field()?.parse().map_err(|_| invalid())?,
+        };
+        let entry = Entry {
+            size: field()?.parse().map_err(|_| invalid())?,
+            modified: field()?.parse().map_err(|_| invalid())?,
+            digest: Digest::decode_hex(field()?.as_bytes()).ok_or_else(invalid)?,
+            verified,
+        };
+        if fields.next().is_some() {
+            return Err(invalid());
+        }
+        entries.insert(key, entry);
+    }
+    Ok(entries)
+}
+
+/// Returns the modification time of the file in nanoseconds since the Unix epoch.
+fn modified(metadata: &Metadata) -> i64 {
+    metadata
+        .mtime()
+        .saturating_mul(1_000_000_000)
+        .saturating_add(metadata.mtime_nsec())
+}
+
+/// Returns the current time in nanoseconds since the Unix epoch.
+fn now() -> i64 {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    i64::try_from(now.as_nanos()).unwrap_or(i64::MAX)
+}
@@ -0,0 +1,199 @@
+//! This module provides computation of digests of files and directories which can be cancelled.
+//!
+//! A [`Token`] is shared between the computation and the code which decides to stop it, e.g. another thread or a request handler.
+//! The token is checked before every path visited while listing directories, before every file and after every chunk of data, so the computation stops promptly even for very large files.
+//! The asynchronous computation also stops while waiting for the data, e.g. of a slow network file system or a pipe.
+//! A cancelled computation returns [`Error::Cancelled`], which is distinct from the I/O errors.
+//!
+//! The digests are the same as computed by [`chksum`](crate::chksum) for paths.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::thread;
+//! use std::time::Duration;
+//!
+//! use chksum_sha1 as sha1;
+//! use chksum_sha1::cancel::{Error, Token};
+//!
+//! # fn wrapper(path: &Path) {
+//! let token = Token::new();
+//! thread::spawn({
+//!     let token = token.clone();
+//!     move || {
+//!         thread::sleep(Duration::from_secs(10));
+//!         token.cancel();
+//!     }
+//! });
+//! match sha1::cancel::chksum(path, &token) {
+//!     Ok(digest) => println!("{digest}"),
+//!     Err(Error::Cancelled) => eprintln!("cancelled after 10 seconds"),
+//!     Err(error) => eprintln!("{error}"),
+//! }
+//! # }
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+use std::result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::{walk, Digest, PathError, SHA1};
+
+/// A flag which cancels all computations it has been passed to.
+///
+/// Clones of the token share the same flag.
+#[derive(Clone, Debug, Default)]
+pub struct Token {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    /// Wakes the asynchronous computations waiting for the data.
+    #[cfg(feature = "async-runtime-tokio")]
+    notify: tokio::sync::Notify,
+}
+
+impl Token {
+    /// Creates a new token which is not cancelled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests the cancellation of all computations using this token.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+        #[cfg(feature = "async-runtime-tokio")]
+        self.inner.notify.notify_waiters();
+    }
+
+    /// Returns `true` if the cancellation has been requested.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Returns [`Error::Cancelled`] if the cancellation has been requested.
+    fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            Err(Error::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Waits until the cancellation is requested, then returns [`Error::Cancelled`].
+    #[cfg(feature = "async-runtime-tokio")]
+    async fn cancelled(&self) -> Error {
+        let notified = self.inner.notify.notified();
+        let mut notified = std::pin::pin!(notified);
+        // the waiter is registered before checking the flag, so a concurrent cancellation isn't missed
+        notified.as_mut().enable();
+        if !self.is_cancelled() {
+            notified.await;
+        }
+        Error::Cancelled
+    }
+}
+
+/// An error returned by a computation which can be cancelled.
+#[derive(Debug)]
+pub enum Error {
+    /// The computation has been cancelled with the [`Token`].
+    Cancelled,
+    /// The computation has failed.
+    Chksum(crate::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "Computation cancelled"),
+            Self::Chksum(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Cancelled => None,
+            Self::Chksum(error) => Some(error),
+        }
+    }
+}
+
+impl From<crate::Error> for Error {
+    fn from(error: crate::Error) -> Self {
+        Self::Chksum(error)
+    }
+}
+
+impl From<PathError> for Error {
+    fn from(error: PathError) -> Self {
+        Self::Chksum(error.into())
+    }
+}
+
+/// A specialized [`Result`](std::result::Result) type for computations which can be cancelled.
+pub type Result<T> = result::Result<T, Error>;
+
+/// Computes the hash of the file or directory, unless the token is cancelled.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use chksum_sha1 as sha1;
+/// use chksum_sha1::cancel::{Error, Token};
+///
+/// # fn wrapper(path: &Path) {
+/// let token = Token::new();
+/// token.cancel();
+/// let result = sha1::cancel::chksum(path, &token);
+/// assert!(matches!(result, Err(Error::Cancelled)));
+/// # }
+/// ```
+pub fn chksum(path: impl AsRef<Path>, token: &Token) -> Result<Digest> {
+    token.check()?;
+    let files = walk::files_until(path.as_ref(), |error| Err(Error::from(error)), || token.check())?;
+    let mut hash = SHA1::new();
+    for (file, _) in &files {
+        token.check()?;
+        walk::update(file, &mut hash, |_| token.check())?;
+    }
+    Ok(hash.digest())
+}
+
+/// Computes the hash of the file or directory, unless the token is cancelled.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use chksum_sha1 as sha1;
+/// use chksum_sha1::cancel::{Error, Token};
+///
+/// # async fn wrapper(path: &Path) {
+/// let token = Token::new();
+/// token.cancel();
+/// let result = sha1::cancel::async_chksum(path, &token).await;
+/// assert!(matches!(result, Err(Error::Cancelled)));
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum(path: impl AsRef<Path>, token: &Token) -> Result<Digest> {
+    token.check()?;
+    let files = walk::async_files_until(path.as_ref(), |error| Err(Error::from(error)), || token.check()).await?;
+    let mut hash = SHA1::new();
+    for (file, _) in &files {
+        token.check()?;
+        walk::async_update_until(file, &mut hash, |_| token.check(), token.cancelled()).await?;
+    }
+    Ok(hash.digest())
+}
@@ -0,0 +1,415 @@
+//! This module provides computation of digests for fixed-size chunks of the input.
+//!
+//! Instead of a single digest for the whole input, a separate digest is produced for every `size` bytes of the data.
+//! The last chunk may be shorter than `size` bytes, and an empty input produces no digests at all.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use chksum_sha1::Result;
+//! use chksum_sha1 as sha1;
+//!
+//! # fn wrapper() -> Result<()> {
+//! let data = &b"example data"[..];
+//! let digests = sha1::chunk::chksum(data, 8)?;
+//! assert_eq!(digests.len(), 2);
+//! assert_eq!(
+//!     digests[0].to_hex_lowercase(),
+//!     "627dac8706cf475c6b7c03d290f5bcf5f2294ddb"
+//! );
+//! # Ok(())
+//! # }
+//! ```
+
+use std::cmp::min;
+use std::io::{self, ErrorKind, Read, Write};
+use std::path::Path;
+
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::{walk, Digest, Error, Result, SHA1};
+
+/// The size of the buffer used to read data.
+const BUFFER_LENGTH: usize = 8 * 1024;
+
+/// Computes digests of the consecutive `size`-byte chunks of the data read from the reader.
+///
+/// # Panics
+///
+/// Panics if `size` is zero.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::File;
+///
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let file = File::open(path)?;
+/// let digests = sha1::chunk::chksum(file, 256 * 1024)?;
+/// for digest in digests {
+///     println!("{digest}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum(reader: impl Read, size: usize) -> Result<Vec<Digest>> {
+    chunks(reader, size).collect()
+}
+
+/// Computes digests of the consecutive `size`-byte chunks of the file or of all files of the directory.
+///
+/// The files of a directory are processed the same way as by [`chksum`](crate::chksum), i.e. as the concatenation of their data, so a chunk may span many files.
+///
+/// # Panics
+///
+/// Panics if `size` is zero.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let digests = sha1::chunk::chksum_path(path, 256 * 1024)?;
+/// println!("{} chunks", digests.len());
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum_path(path: impl AsRef<Path>, size: usize) -> Result<Vec<Digest>> {
+    let mut chunker = Chunker::new(size);
+    let mut digests = Vec::new();
+    let files = walk::files(path.as_ref(), |error| Err(Error::from(error)))?;
+    for (file, _) in &files {
+        walk::read(file, |data| {
+            chunker.update(data, |digest| digests.push(digest));
+            Ok::<_, Error>(())
+        })?;
+    }
+    digests.extend(chunker.finish());
+    Ok(digests)
+}
+
+/// Computes digests of the consecutive `size`-byte chunks of the data read from the reader.
+///
+/// # Panics
+///
+/// Panics if `size` is zero.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+/// use tokio::fs::File;
+///
+/// # async fn wrapper(path: &Path) -> Result<()> {
+/// let file = File::open(path).await?;
+/// let digests = sha1::chunk::async_chksum(file, 256 * 1024).await?;
+/// for digest in digests {
+///     println!("{digest}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum(mut reader: impl AsyncRead + Unpin, size: usize) -> Result<Vec<Digest>> {
+    let mut chunker = Chunker::new(size);
+    let mut digests = Vec::new();
+    let mut buffer = [0u8; BUFFER_LENGTH];
+    loop {
+        let length = reader.read(&mut buffer).await?;
+        if length == 0 {
+            break;
+        }
+        chunker.update(&buffer[..length], |digest| digests.push(digest));
+    }
+    digests.extend(chunker.finish());
+    Ok(digests)
+}
+
+/// Computes digests of the consecutive `size`-byte chunks of the file or of all files of the directory.
+///
+/// The files of a directory are processed the same way as by [`async_chksum`](crate::async_chksum), i.e. as the concatenation of their data, so a chunk may span many files.
+///
+/// # Panics
+///
+/// Panics if `size` is zero.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # async fn wrapper(path: &Path) -> Result<()> {
+/// let digests = sha1::chunk::async_chksum_path(path, 256 * 1024).await?;
+/// println!("{} chunks", digests.len());
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum_path(path: impl AsRef<Path>, size: usize) -> Result<Vec<Digest>> {
+    let mut chunker = Chunker::new(size);
+    let mut digests = Vec::new();
+    let files = walk::async_files(path.as_ref(), |error| Err(Error::from(error))).await?;
+    for (file, _) in &files {
+        walk::async_read(file, |data| {
+            chunker.update(data, |digest| digests.push(digest));
+            Ok::<_, Error>(())
+        })
+        .await?;
+    }
+    digests.extend(chunker.finish());
+    Ok(digests)
+}
+
+/// Creates new [`Chunks`] iterator.
+///
+/// # Panics
+///
+/// Panics if `size` is zero.
+///
+/// # Example
+///
+/// ```rust
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper() -> Result<()> {
+/// let data = &b"example data"[..];
+/// let digests = sha1::chunk::chunks(data, 8).collect::<Result<Vec<_>>>()?;
+/// assert_eq!(digests.len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+pub fn chunks<R>(inner: R, size: usize) -> Chunks<R>
+where
+    R: Read,
+{
+    Chunks::new(inner, size)
+}
+
+/// Creates new [`Writer`] which calls `callback` with the digest of every chunk written through it.
+///
+/// # Panics
+///
+/// Panics if `size` is zero.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Write;
+///
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper() -> Result<()> {
+/// let mut digests = Vec::new();
+/// let mut writer = sha1::chunk::writer(Vec::new(), 8, |digest| digests.push(digest));
+/// writer.write_all(b"example data")?;
+/// let buffer = writer.finish();
+/// assert_eq!(buffer, b"example data");
+/// assert_eq!(digests.len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+pub fn writer<W, F>(inner: W, size: usize, callback: F) -> Writer<W, F>
+where
+    W: Write,
+    F: FnMut(Digest),
+{
+    Writer::new(inner, size, callback)
+}
+
+/// A splitter of the incoming data into fixed-size chunks.
+#[derive(Clone, Debug)]
+pub(crate) struct Chunker {
+    size: usize,
+    hash: SHA1,
+    length: usize,
+}
+
+impl Chunker {
+    pub(crate) fn new(size: usize) -> Self {
+        assert!(size > 0, "chunk size must be greater than zero");
+        let hash = SHA1::new();
+        let length = 0;
+        Self { size, hash, length }
+    }
+
+    /// Processes the data and calls `callback` for every completed chunk.
+    pub(crate) fn update(&mut self, mut data: &[u8], mut callback: impl FnMut(Digest)) {
+        while !data.is_empty() {
+            let missing = min(self.size - self.length, data.len());
+            let (chunk, rest) = data.split_at(missing);
+            self.hash.update(chunk);
+            self.length += chunk.len();
+            if self.length == self.size {
+                callback(self.hash.digest());
+                self.hash.reset();
+                self.length = 0;
+            }
+            data = rest;
+        }
+    }
+
+    /// Returns the digest of the last, incomplete chunk (if any).
+    pub(crate) fn finish(&mut self) -> Option<Digest> {
+        if self.length == 0 {
+            return None;
+        }
+        let digest = self.hash.digest();
+        self.hash.reset();
+        self.length = 0;
+        Some(digest)
+    }
+}
+
+/// An iterator over digests of the fixed-size chunks read from the inner reader.
+///
+/// Check [`chunks`] function for usage examples.
+#[derive(Debug)]
+pub struct Chunks<R>
+where
+    R: Read,
+{
+    inner: R,
+    size: usize,
+    finished: bool,
+}
+
+impl<R> Chunks<R>
+where
+    R: Read,
+{
+    /// Creates new iterator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn new(inner: R, size: usize) -> Self {
+        assert!(size > 0, "chunk size must be greater than zero");
+        let finished = false;
+        Self { inner, size, finished }
+    }
+
+    /// Unwraps this `Chunks<R>`, returning the underlying reader.
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        let Self { inner, .. } = self;
+        inner
+    }
+}
+
+impl<R> Iterator for Chunks<R>
+where
+    R: Read,
+{
+    type Item = Result<Digest>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let mut hash = SHA1::new();
+        let mut length = 0;
+        let mut buffer = [0u8; BUFFER_LENGTH];
+        while length < self.size {
+            let limit = min(buffer.len(), self.size - length);
+            match self.inner.read(&mut buffer[..limit]) {
+                Ok(0) => {
+                    self.finished = true;
+                    break;
+                },
+                Ok(n) => {
+                    hash.update(&buffer[..n]);
+                    length += n;
+                },
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    self.finished = true;
+                    return Some(Err(error.into()));
+                },
+            }
+        }
+
+        if length == 0 {
+            None
+        } else {
+            Some(Ok(hash.digest()))
+        }
+    }
+}
+
+/// A writer which computes digests of the fixed-size chunks while writing the data.
+///
+/// Check [`writer`] function for usage examples.
+pub struct Writer<W, F>
+where
+    W: Write,
+    F: FnMut(Digest),
+{
+    inner: W,
+    chunker: Chunker,
+    callback: F,
+}
+
+impl<W, F> Writer<W, F>
+where
+    W: Write,
+    F: FnMut(Digest),
+{
+    /// Creates new writer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn new(inner: W, size: usize, callback: F) -> Self {
+        let chunker = Chunker::new(size);
+        Self {
+            inner,
+            chunker,
+            callback,
+        }
+    }
+
+    /// Reports the digest of the last, incomplete chunk (if any) and returns the underlying writer.
+    #[must_use]
+    pub fn finish(self) -> W {
+        let Self {
+            inner,
+            mut chunker,
+            mut callback,
+        } = self;
+        if let Some(digest) = chunker.finish() {
+            callback(digest);
+        }
+        inner
+    }
+}
+
+impl<W, F> Write for Writer<W, F>
+where
+    W: Write,
+    F: FnMut(Digest),
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.chunker.update(&buf[..n], &mut self.callback);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,274 @@
+//! The SHA-1 compression function with the portable and the hardware-accelerated backends.
+//!
+//! The accelerated backends are compiled in only with the `acceleration` Cargo feature and are selected at runtime, when the CPU supports the required instructions.
+
+#[cfg(all(feature = "acceleration", target_arch = "aarch64"))]
+mod aarch64;
+#[cfg(all(feature = "acceleration", any(target_arch = "x86", target_arch = "x86_64")))]
+mod x86;
+
+/// The length of a single block in bytes.
+pub(crate) const BLOCK_LENGTH_BYTES: usize = 64;
+
+/// The number of independent blocks processed at once by [`compress_lanes`].
+pub(crate) const LANES: usize = 8;
+
+/// The round constants, one for every 20 rounds.
+#[allow(clippy::unreadable_literal)]
+const K: [u32; 4] = [0x5A827999, 0x6ED9EBA1, 0x8F1BBCDC, 0xCA62C1D6];
+
+/// Processes the blocks of data with the fastest backend supported by the CPU.
+///
+/// The length of `blocks` must be a multiple of [`BLOCK_LENGTH_BYTES`].
+pub(crate) fn compress(state: &mut [u32; 5], blocks: &[u8]) {
+    debug_assert_eq!(blocks.len() % BLOCK_LENGTH_BYTES, 0, "blocks must be complete");
+    #[cfg(all(feature = "acceleration", any(target_arch = "x86", target_arch = "x86_64")))]
+    if x86::is_supported() {
+        return x86::compress(state, blocks);
+    }
+    #[cfg(all(feature = "acceleration", target_arch = "aarch64"))]
+    if aarch64::is_supported() {
+        return aarch64::compress(state, blocks);
+    }
+    portable(state, blocks);
+}
+
+/// Processes the blocks of data without any CPU-specific instructions.
+pub(crate) fn portable(state: &mut [u32; 5], blocks: &[u8]) {
+    for block in blocks.chunks_exact(BLOCK_LENGTH_BYTES) {
+        let mut w = [0u32; 80];
+        for (w, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+            *w = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        for t in 16..80 {
+            w[t] = (w[t - 3] ^ w[t - 8] ^ w[t - 14] ^ w[t - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = *state;
+        macro_rules! rounds {
+            ($range:expr, $k:expr, |$b:ident, $c:ident, $d:ident| $f:expr) => {
+                for w in &w[$range] {
+                    let ($b, $c, $d) = (b, c, d);
+                    let temp = a
+                        .rotate_left(5)
+                        .wrapping_add($f)
+                        .wrapping_add(e)
+                        .wrapping_add($k)
+                        .wrapping_add(*w);
+                    e = d;
+                    d = c;
+                    c = b.rotate_left(30);
+                    b = a;
+                    a = temp;
+                }
+            };
+        }
+        rounds!(0..20, K[0], |b, c, d| (b & c) | (!b & d));
+        rounds!(20..40, K[1], |b, c, d| b ^ c ^ d);
+        rounds!(40..60, K[2], |b, c, d| (b & c) | (b & d) | (c & d));
+        rounds!(60..80, K[3], |b, c, d| b ^ c ^ d);
+
+        for (state, value) in state.iter_mut().zip([a, b, c, d, e]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+}
+
+/// Returns `true` if processing the blocks of several inputs at once with [`compress_lanes`] is faster than processing the inputs one by one with [`compress`].
+///
+/// The lanes are faster only when they are vectorized and the CPU doesn't provide dedicated SHA-1 instructions.
+pub(crate) fn is_lanes_preferred() -> bool {
+    #[cfg(all(feature = "acceleration", any(target_arch = "x86", target_arch = "x86_64")))]
+    if x86::is_avx2_supported() && !x86::is_supported() {
+        return true;
+    }
+    false
+}
+
+/// Processes a single block for every lane with the fastest backend supported by the CPU.
+///
+/// Every chaining value is stored for all lanes at once, so the rounds of the lanes can be executed with SIMD instructions.
+pub(crate) fn compress_lanes(state: &mut [[u32; LANES]; 5], blocks: &[[u8; BLOCK_LENGTH_BYTES]; LANES]) {
+    #[cfg(all(feature = "acceleration", any(target_arch = "x86", target_arch = "x86_64")))]
+    if x86::is_avx2_supported() {
+        return x86::compress_lanes(state, blocks);
+    }
+    portable_lanes(state, blocks);
+}
+
+/// The values of a single variable for all lanes.
+type Lanes = [u32; LANES];
+
+/// Applies the operation to the values of all lanes.
+#[inline(always)]
+fn map2(x: Lanes, y: Lanes, operation: impl Fn(u32, u32) -> u32) -> Lanes {
+    let mut result = [0u32; LANES];
+    for ((result, x), y) in result.iter_mut().zip(x).zip(y) {
+        *result = operation(x, y);
+    }
+    result
+}
+
+/// Applies the operation to the values of all lanes.
+#[inline(always)]
+fn map3(x: Lanes, y: Lanes, z: Lanes, operation: impl Fn(u32, u32, u32) -> u32) -> Lanes {
+    let mut result = [0u32; LANES];
+    for (((result, x), y), z) in result.iter_mut().zip(x).zip(y).zip(z) {
+        *result = operation(x, y, z);
+    }
+    result
+}
+
+#[inline(always)]
+fn add(x: Lanes, y: Lanes) -> Lanes {
+    map2(x, y, u32::wrapping_add)
+}
+
+#[inline(always)]
+fn xor(x: Lanes, y: Lanes) -> Lanes {
+    map2(x, y, |x, y| x ^ y)
+}
+
+#[inline(always)]
+fn rotate_left<const N: u32>(x: Lanes) -> Lanes {
+    x.map(|x| x.rotate_left(N))
+}
+
+/// Processes a single block for every lane, relying on the compiler to vectorize the operations on all lanes.
+#[inline(always)]
+pub(crate) fn portable_lanes(state: &mut [Lanes; 5], blocks: &[[u8; BLOCK_LENGTH_BYTES]; LANES]) {
+    let mut w = [[0u32; LANES]; 16];
+    for (t, w) in w.iter_mut().enumerate() {
+        for (w, block) in w.iter_mut().zip(blocks) {
+            *w = u32::from_be_bytes([block[4 * t], block[4 * t + 1], block[4 * t + 2], block[4 * t + 3]]);
+        }
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+    macro_rules! rounds {
+        ($range:expr, $k:expr, |$b:ident, $c:ident, $d:ident| $f:expr) => {
+            for t in $range {
+                let w = if t < 16 {
+                    w[t]
+                } else {
+                    // the message schedule is kept only for the last 16 rounds
+                    let word = rotate_left::<1>(xor(
+                        xor(w[(t - 3) % 16], w[(t - 8) % 16]),
+                        xor(w[(t - 14) % 16], w[t % 16]),
+                    ));
+                    w[t % 16] = word;
+                    word
+                };
+                let ($b, $c, $d) = (b, c, d);
+                let temp = add(add(rotate_left::<5>(a), $f), add(add(e, [$k; LANES]), w));
+                e = d;
+                d = c;
+                c = rotate_left::<30>(b);
+                b = a;
+                a = temp;
+            }
+        };
+    }
+    rounds!(0..20, K[0], |b, c, d| map3(b, c, d, |b, c, d| (b & c) | (!b & d)));
+    rounds!(20..40, K[1], |b, c, d| map3(b, c, d, |b, c, d| b ^ c ^ d));
+    rounds!(40..60, K[2], |b, c, d| {
+        map3(b, c, d, |b, c, d| (b & c) | (b & d) | (c & d))
+    });
+    rounds!(60..80, K[3], |b, c, d| map3(b, c, d, |b, c, d| b ^ c ^ d));
+
+    for (state, values) in state.iter_mut().zip([a, b, c, d, e]) {
+        *state = add(*state, values);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::unreadable_literal)]
+    const INITIAL: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+    /// Checks that the backend produces the same states as the portable one.
+    #[allow(clippy::unreadable_literal)]
+    fn check(backend: fn(&mut [u32; 5], &[u8])) {
+        // a single padded block of the message `abc`
+        let mut block = [0u8; BLOCK_LENGTH_BYTES];
+        block[..4].copy_from_slice(b"abc\x80");
+        block[63] = 24;
+        let mut state = INITIAL;
+        backend(&mut state, &block);
+        assert_eq!(state, [0xA9993E36, 0x4706816A, 0xBA3E2571, 0x7850C26C, 0x9CD0D89D]);
+
+        let mut data = [0u8; 7 * BLOCK_LENGTH_BYTES];
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = (i * 31 % 251) as u8;
+        }
+        let mut expected = INITIAL;
+        portable(&mut expected, &data);
+
+        // all blocks at once
+        let mut state = INITIAL;
+        backend(&mut state, &data);
+        assert_eq!(state, expected);
+
+        // block by block
+        let mut state = INITIAL;
+        for block in data.chunks_exact(BLOCK_LENGTH_BYTES) {
+            backend(&mut state, block);
+        }
+        assert_eq!(state, expected);
+
+        // no blocks at all
+        let mut state = INITIAL;
+        backend(&mut state, &[]);
+        assert_eq!(state, INITIAL);
+    }
+
+    /// Checks that the lanes backend produces the same states as the portable one.
+    fn check_lanes(backend: fn(&mut [[u32; LANES]; 5], &[[u8; BLOCK_LENGTH_BYTES]; LANES])) {
+        let mut blocks = [[0u8; BLOCK_LENGTH_BYTES]; LANES];
+        for (lane, block) in blocks.iter_mut().enumerate() {
+            for (i, byte) in block.iter_mut().enumerate() {
+                *byte = (i * 31 + lane * 7) as u8;
+            }
+        }
+        let mut state = [[0u32; LANES]; 5];
+        for (state, value) in state.iter_mut().zip(INITIAL) {
+            *state = [value; LANES];
+        }
+        backend(&mut state, &blocks);
+        backend(&mut state, &blocks);
+
+        for (lane, block) in blocks.iter().enumerate() {
+            let mut expected = INITIAL;
+            portable(&mut expected, block);
+            portable(&mut expected, block);
+            assert_eq!(state.map(|state| state[lane]), expected);
+        }
+    }
+
+    #[test]
+    fn portable_backend() {
+        check(portable);
+        check_lanes(portable_lanes);
+    }
+
+    #[cfg(all(feature = "acceleration", any(target_arch = "x86", target_arch = "x86_64")))]
+    #[test]
+    fn x86_backend() {
+        if x86::is_supported() {
+            check(x86::compress);
+        }
+        if x86::is_avx2_supported() {
+            check_lanes(x86::compress_lanes);
+        }
+    }
+
+    #[cfg(all(feature = "acceleration", target_arch = "aarch64"))]
+    #[test]
+    fn aarch64_backend() {
+        if aarch64::is_supported() {
+            check(aarch64::compress);
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! The SHA-1 compression function using the ARMv8 cryptographic extensions.
+
+#![allow(unsafe_code)]
+
+use core::arch::aarch64::*;
+
+use super::{BLOCK_LENGTH_BYTES, K};
+
+// the `sha2` target feature enables both SHA-1 and SHA-256 instructions
+cpufeatures::new!(sha, "sha2");
+
+/// Returns `true` if the CPU supports the required instructions.
+pub(super) fn is_supported() -> bool {
+    sha::get()
+}
+
+/// Processes the blocks of data.
+///
+/// # Panics
+///
+/// Panics if the CPU doesn't support the required instructions.
+pub(super) fn compress(state: &mut [u32; 5], blocks: &[u8]) {
+    assert!(is_supported(), "ARMv8 SHA-1 instructions must be supported");
+    // SAFETY: the required CPU features have been detected above
+    unsafe { compress_blocks(state, blocks) }
+}
+
+#[target_feature(enable = "sha2")]
+unsafe fn compress_blocks(state: &mut [u32; 5], blocks: &[u8]) {
+    let mut abcd = vld1q_u32(state.as_ptr());
+    let mut e = state[4];
+
+    for block in blocks.chunks_exact(BLOCK_LENGTH_BYTES) {
+        macro_rules! load {
+            ($index:literal) => {
+                // converts big-endian words
+                vreinterpretq_u32_u8(vrev32q_u8(vld1q_u8(block[16 * $index..].as_ptr())))
+            };
+        }
+        // the words of the message schedule for the current and the 3 following groups of 4 rounds
+        let (mut w0, mut w1, mut w2, mut w3) = (load!(0), load!(1), load!(2), load!(3));
+
+        let (abcd_saved, e_saved) = (abcd, e);
+        macro_rules! rounds {
+            ($function:ident, $k:expr) => {
+                let k = vdupq_n_u32($k);
+                for _ in 0..5 {
+                    // the fifth working variable of the next 4 rounds is the rotated first one of the current rounds
+                    let e_next = vsha1h_u32(vgetq_lane_u32(abcd, 0));
+                    abcd = $function(abcd, e, vaddq_u32(w0, k));
+                    e = e_next;
+                    let w4 = vsha1su1q_u32(vsha1su0q_u32(w0, w1, w2), w3);
+                    (w0, w1, w2, w3) = (w1, w2, w3, w4);
+                }
+            };
+        }
+        rounds!(vsha1cq_u32, K[0]);
+        rounds!(vsha1pq_u32, K[1]);
+        rounds!(vsha1mq_u32, K[2]);
+        rounds!(vsha1pq_u32, K[3]);
+
+        abcd = vaddq_u32(abcd, abcd_saved);
+        e = e.wrapping_add(e_saved);
+    }
+
+    vst1q_u32(state.as_mut_ptr(), abcd);
+    state[4] = e;
+}
@@ -0,0 +1,100 @@
+//! The SHA-1 compression function using the x86 SHA extensions (SHA-NI).
+
+#![allow(unsafe_code)]
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+use super::{BLOCK_LENGTH_BYTES, LANES};
+
+cpufeatures::new!(sha_ni, "sha", "sse2", "ssse3", "sse4.1");
+cpufeatures::new!(avx2, "avx2");
+
+/// Returns `true` if the CPU supports the required instructions.
+pub(super) fn is_supported() -> bool {
+    sha_ni::get()
+}
+
+/// Returns `true` if the CPU supports the instructions required by [`compress_lanes`].
+pub(super) fn is_avx2_supported() -> bool {
+    avx2::get()
+}
+
+/// Processes the blocks of data.
+///
+/// # Panics
+///
+/// Panics if the CPU doesn't support the required instructions.
+pub(super) fn compress(state: &mut [u32; 5], blocks: &[u8]) {
+    assert!(is_supported(), "SHA-NI instructions must be supported");
+    // SAFETY: the required CPU features have been detected above
+    unsafe { compress_blocks(state, blocks) }
+}
+
+#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+#[target_feature(enable = "sha,sse2,ssse3,sse4.1")]
+unsafe fn compress_blocks(state: &mut [u32; 5], blocks: &[u8]) {
+    // reverses the order of all bytes, which converts big-endian words and puts the first word into the highest lane
+    let mask = _mm_set_epi64x(0x0001_0203_0405_0607, 0x0809_0A0B_0C0D_0E0F);
+
+    let mut abcd = _mm_set_epi32(state[0] as i32, state[1] as i32, state[2] as i32, state[3] as i32);
+    let mut e = _mm_set_epi32(state[4] as i32, 0, 0, 0);
+
+    for block in blocks.chunks_exact(BLOCK_LENGTH_BYTES) {
+        macro_rules! load {
+            ($index:literal) => {
+                _mm_shuffle_epi8(_mm_loadu_si128(block[16 * $index..].as_ptr().cast()), mask)
+            };
+        }
+        // the words of the message schedule for the current and the 3 following groups of 4 rounds
+        let (mut w0, mut w1, mut w2, mut w3) = (load!(0), load!(1), load!(2), load!(3));
+
+        let (abcd_saved, e_saved) = (abcd, e);
+        // the fifth working variable of the current 4 rounds, added to the words of the schedule
+        let mut e_w = _mm_add_epi32(e, w0);
+        let mut previous = abcd;
+        macro_rules! rounds {
+            ($function:literal) => {
+                for _ in 0..5 {
+                    previous = abcd;
+                    abcd = _mm_sha1rnds4_epu32(abcd, e_w, $function);
+                    let w4 = _mm_sha1msg2_epu32(_mm_xor_si128(_mm_sha1msg1_epu32(w0, w1), w2), w3);
+                    (w0, w1, w2, w3) = (w1, w2, w3, w4);
+                    e_w = _mm_sha1nexte_epu32(previous, w0);
+                }
+            };
+        }
+        rounds!(0);
+        rounds!(1);
+        rounds!(2);
+        rounds!(3);
+
+        abcd = _mm_add_epi32(abcd, abcd_saved);
+        e = _mm_sha1nexte_epu32(previous, e_saved);
+    }
+
+    state[0] = _mm_extract_epi32(abcd, 3) as u32;
+    state[1] = _mm_extract_epi32(abcd, 2) as u32;
+    state[2] = _mm_extract_epi32(abcd, 1) as u32;
+    state[3] = _mm_extract_epi32(abcd, 0) as u32;
+    state[4] = _mm_extract_epi32(e, 3) as u32;
+}
+
+/// Processes a single block for every lane.
+///
+/// # Panics
+///
+/// Panics if the CPU doesn't support the required instructions.
+pub(super) fn compress_lanes(state: &mut [[u32; LANES]; 5], blocks: &[[u8; BLOCK_LENGTH_BYTES]; LANES]) {
+    assert!(is_avx2_supported(), "AVX2 instructions must be supported");
+    // SAFETY: the required CPU features have been detected above
+    unsafe { compress_lanes_avx2(state, blocks) }
+}
+
+/// Compiles the portable implementation with the AVX2 instructions enabled, which allows to process 8 lanes at once.
+#[target_feature(enable = "avx2")]
+unsafe fn compress_lanes_avx2(state: &mut [[u32; LANES]; 5], blocks: &[[u8; BLOCK_LENGTH_BYTES]; LANES]) {
+    super::portable_lanes(state, blocks);
+}
@@ -0,0 +1,244 @@
+//! This module provides computation of digests of decompressed data, e.g. to verify a `.tar.gz` file against the published digest of the `.tar` file.
+//!
+//! The format is either given explicitly or detected from the leading bytes of the data, in which case data which isn't compressed in any supported format is hashed as it is.
+//!
+//! # Formats
+//!
+//! | Format    | Cargo feature | Concatenation             |
+//! |-----------|---------------|---------------------------|
+//! | gzip      | `gzip`        | all members are decoded   |
+//! | Zstandard | `zstd`        | all frames are decoded    |
+//! | xz        | `xz`          | all streams are decoded   |
+//!
+//! # Enabling
+//!
+//! This module is optional and can be enabled using any of the `gzip`, `zstd` and `xz` Cargo features, each enabling the respective format.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::io::stdin;
+//!
+//! # use chksum_sha1::Result;
+//! use chksum_sha1 as sha1;
+//! use chksum_sha1::decompress::Decompressed;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! // the same digest for `archive.tar` and `archive.tar.gz`
+//! let digest = sha1::decompress::chksum(path, None)?;
+//! println!("{digest}");
+//!
+//! let digest = sha1::chksum(Decompressed::new(stdin()))?;
+//! println!("{digest}");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, ErrorKind, IsTerminal, Read};
+use std::path::Path;
+
+use crate::{core, walk, Digest, Error, Operation, PathError, Result, SHA1};
+
+/// The length of the longest magic number of the supported formats.
+const MAGIC_LENGTH: usize = 6;
+
+/// A compression format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// The gzip format, defined in [RFC 1952](https://www.rfc-editor.org/rfc/rfc1952).
+    #[cfg(feature = "gzip")]
+    Gzip,
+    /// The Zstandard format, defined in [RFC 8878](https://www.rfc-editor.org/rfc/rfc8878).
+    #[cfg(feature = "zstd")]
+    Zstd,
+    /// The xz format of XZ Utils.
+    #[cfg(feature = "xz")]
+    Xz,
+}
+
+impl Format {
+    /// All formats enabled by Cargo features.
+    const ALL: &'static [Self] = &[
+        #[cfg(feature = "gzip")]
+        Self::Gzip,
+        #[cfg(feature = "zstd")]
+        Self::Zstd,
+        #[cfg(feature = "xz")]
+        Self::Xz,
+    ];
+
+    /// Detects the format from the leading bytes of the data.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha1::decompress::Format;
+    ///
+    /// # #[cfg(feature = "gzip")]
+    /// assert_eq!(
+    ///     Format::detect(&[0x1F, 0x8B, 0x08, 0x00]),
+    ///     Some(Format::Gzip)
+    /// );
+    /// assert_eq!(Format::detect(b"example data"), None);
+    /// ```
+    #[must_use]
+    pub fn detect(header: &[u8]) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|format| header.starts_with(format.magic()))
+    }
+
+    /// Returns the magic number which starts the data in this format.
+    fn magic(self) -> &'static [u8] {
+        match self {
+            #[cfg(feature = "gzip")]
+            Self::Gzip => &[0x1F, 0x8B],
+            #[cfg(feature = "zstd")]
+            Self::Zstd => &[0x28, 0xB5, 0x2F, 0xFD],
+            #[cfg(feature = "xz")]
+            Self::Xz => &[0xFD, 0x37, 0x7A, 0x58, 0x5A, 0x00],
+        }
+    }
+}
+
+/// A wrapper of a reader which makes [`chksum`](crate::chksum) hash the decompressed data.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::File;
+///
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+/// use chksum_sha1::decompress::{Decompressed, Format};
+///
+/// # #[cfg(feature = "gzip")]
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let file = File::open(path)?;
+/// let digest = sha1::chksum(Decompressed::with_format(file, Format::Gzip))?;
+/// println!("{digest}");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Decompressed<R> {
+    reader: R,
+    format: Option<Format>,
+}
+
+impl<R> Decompressed<R> {
+    /// Creates a wrapper which detects the format from the leading bytes of the data.
+    #[must_use]
+    pub fn new(reader: R) -> Self {
+        let format = None;
+        Self { reader, format }
+    }
+
+    /// Creates a wrapper which decompresses the data in the given format.
+    #[must_use]
+    pub fn with_format(reader: R, format: Format) -> Self {
+        let format = Some(format);
+        Self { reader, format }
+    }
+
+    /// Returns the wrapped reader.
+    #[must_use]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R> core::Chksumable for Decompressed<R>
+where
+    R: Read,
+{
+    fn chksum_with<H>(&mut self, hash: &mut H) -> Result<()>
+    where
+        H: core::Hash,
+    {
+        update(&mut self.reader, self.format, hash)?;
+        Ok(())
+    }
+}
+
+/// Computes the hash of the decompressed data of the file or of all files of the directory.
+///
+/// The files are processed in the same order as by [`chksum`](crate::chksum), and every file is decompressed separately.
+/// If `format` is `None`, the format of every file is detected and files which aren't compressed are hashed as they are.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let digest = sha1::decompress::chksum(path, None)?;
+/// println!("{digest}");
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum(path: impl AsRef<Path>, format: Option<Format>) -> Result<Digest> {
+    let files = walk::files(path.as_ref(), |error| Err(Error::from(error)))?;
+    let mut hash = SHA1::new();
+    for (path, _) in &files {
+        let file = File::open(path).map_err(|error| PathError::new(Operation::Open, path, error))?;
+        if file.is_terminal() {
+            return Err(PathError::new(Operation::Read, path, Error::IsTerminal).into());
+        }
+        update(file, format, &mut hash).map_err(|error| PathError::new(Operation::Read, path, error))?;
+    }
+    Ok(hash.digest())
+}
+
+/// Updates the hash with the decompressed data of the reader.
+fn update<H>(mut reader: impl Read, format: Option<Format>, hash: &mut H) -> io::Result<()>
+where
+    H: core::Hash,
+{
+    // the header is read up front, as a single read may return fewer bytes than needed for detection
+    let mut header = [0u8; MAGIC_LENGTH];
+    let mut length = 0;
+    while length < header.len() {
+        match reader.read(&mut header[length..]) {
+            Ok(0) => break,
+            Ok(n) => length += n,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        }
+    }
+    let header = &header[..length];
+    let format = format.or_else(|| Format::detect(header));
+    let reader = BufReader::new(Cursor::new(header).chain(reader));
+
+    match format {
+        None => copy(reader, hash),
+        #[cfg(feature = "gzip")]
+        Some(Format::Gzip) => copy(flate2::bufread::MultiGzDecoder::new(reader), hash),
+        #[cfg(feature = "zstd")]
+        Some(Format::Zstd) => copy(zstd::stream::read::Decoder::with_buffer(reader)?, hash),
+        #[cfg(feature = "xz")]
+        Some(Format::Xz) => copy(liblzma::bufread::XzDecoder::new_multi_decoder(reader), hash),
+    }
+}
+
+/// Updates the hash with all data of the reader.
+fn copy<H>(mut reader: impl Read, hash: &mut H) -> io::Result<()>
+where
+    H: core::Hash,
+{
+    let mut buffer = [0u8; 8 * 1024];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(length) => hash.update(&buffer[..length]),
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        }
+    }
+}
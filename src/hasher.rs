@@ -0,0 +1,410 @@
+//! This module provides stable digests of structured values and a [`core::hash::Hasher`] adapter.
+//!
+//! The [`hash_value`] function computes the digest of any value implementing the [`StableHash`] trait.
+//! The encoding of the values is defined by this crate, so it doesn't depend on the platform or on the version of Rust, and the digests can be used as content-addressed keys stored across processes and machines.
+//!
+//! # Encoding
+//!
+//! Every value feeds the underlying [`SHA1`] instance with the following bytes:
+//!
+//! | Type                                          | Bytes                                                                      |
+//! |-----------------------------------------------|----------------------------------------------------------------------------|
+//! | `u8`, `u16`, ..., `u128`                      | The integer in little-endian order.                                        |
+//! | `i8`, `i16`, ..., `i128`                      | The integer in two's complement little-endian order.                       |
+//! | `usize`, `isize`                              | The integer widened to `u64` or `i64` in little-endian order.              |
+//! | `bool`                                        | A single byte, `1` for `true` and `0` for `false`.                         |
+//! | `char`                                        | The scalar value as `u32` in little-endian order.                          |
+//! | `str`, `String`                               | The length in bytes as `u64` in little-endian order, then the UTF-8 bytes. |
+//! | `[T]`, `[T; N]`, `Vec<T>`                     | The number of elements as `u64` in little-endian order, then the elements. |
+//! | `Option<T>`                                   | A single byte `0` for `None`, or a single byte `1` followed by the value.  |
+//! | `()`, `(A,)`, `(A, B)`, ..., up to 8 elements | The elements in order, without any prefix.                                 |
+//! | `&T`, `&mut T`, `Box<T>`                      | The referenced value.                                                      |
+//!
+//! The length prefixes make the encoding unambiguous, e.g. `("ab", "c")` and `("a", "bc")` have different digests.
+//! Implement [`StableHash`] for your own types by feeding their fields in a fixed order.
+//!
+//! # Hasher
+//!
+//! [`Hasher`] and [`BuildHasher`] plug SHA-1 into the standard collections.
+//! Their output is not randomized, but the sequence of bytes written to them is determined by the [`Hash`](core::hash::Hash) implementations, and the ones for the standard library types (e.g. `str` or slices) are not guaranteed to stay the same between Rust versions.
+//! Use [`hash_value`] for digests which must stay the same.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//!
+//! use chksum_sha1::hasher::{self, BuildHasher};
+//!
+//! let digest = hasher::hash_value(&(1u32, "example"));
+//! assert_eq!(
+//!     digest.to_hex_lowercase(),
+//!     "8eff260407589b1e4da31a53717d081d2ca9857d"
+//! );
+//!
+//! let mut map = HashMap::with_hasher(BuildHasher);
+//! map.insert("key", "value");
+//! assert_eq!(map.get("key"), Some(&"value"));
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{Digest, SHA1};
+
+/// Calculates the digest of the value using the [encoding](self#encoding) described in this module.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1::hasher;
+///
+/// let digest = hasher::hash_value(&[1u8, 2, 3]);
+/// assert_eq!(
+///     digest.to_hex_lowercase(),
+///     "c823710d9b0f7601f2eb71225ae953be4a76f205"
+/// );
+/// ```
+#[must_use]
+pub fn hash_value<T>(value: &T) -> Digest
+where
+    T: StableHash + ?Sized,
+{
+    let mut hash = SHA1::new();
+    value.stable_hash(&mut hash);
+    hash.digest()
+}
+
+/// A value with the stable byte [encoding](self#encoding) described in this module.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1::hasher::{self, StableHash};
+/// use chksum_sha1::SHA1;
+///
+/// struct Key {
+///     id: u64,
+///     name: String,
+/// }
+///
+/// impl StableHash for Key {
+///     fn stable_hash(&self, hash: &mut SHA1) {
+///         self.id.stable_hash(hash);
+///         self.name.stable_hash(hash);
+///     }
+/// }
+///
+/// let key = Key {
+///     id: 1,
+///     name: String::from("example"),
+/// };
+/// assert_eq!(
+///     hasher::hash_value(&key),
+///     hasher::hash_value(&(1u64, "example"))
+/// );
+/// ```
+pub trait StableHash {
+    /// Updates the hash with the encoding of the value.
+    fn stable_hash(&self, hash: &mut SHA1);
+}
+
+macro_rules! impl_stable_hash_for_integers {
+    ($($type:ty),+) => {
+        $(
+            impl StableHash for $type {
+                fn stable_hash(&self, hash: &mut SHA1) {
+                    hash.update(self.to_le_bytes());
+                }
+            }
+        )+
+    };
+}
+
+impl_stable_hash_for_integers!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl StableHash for usize {
+    fn stable_hash(&self, hash: &mut SHA1) {
+        (*self as u64).stable_hash(hash);
+    }
+}
+
+impl StableHash for isize {
+    fn stable_hash(&self, hash: &mut SHA1) {
+        (*self as i64).stable_hash(hash);
+    }
+}
+
+impl StableHash for bool {
+    fn stable_hash(&self, hash: &mut SHA1) {
+        u8::from(*self).stable_hash(hash);
+    }
+}
+
+impl StableHash for char {
+    fn stable_hash(&self, hash: &mut SHA1) {
+        u32::from(*self).stable_hash(hash);
+    }
+}
+
+impl StableHash for str {
+    fn stable_hash(&self, hash: &mut SHA1) {
+        self.len().stable_hash(hash);
+        hash.update(self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl StableHash for String {
+    fn stable_hash(&self, hash: &mut SHA1) {
+        self.as_str().stable_hash(hash);
+    }
+}
+
+impl<T> StableHash for [T]
+where
+    T: StableHash,
+{
+    fn stable_hash(&self, hash: &mut SHA1) {
+        self.len().stable_hash(hash);
+        for element in self {
+            element.stable_hash(hash);
+        }
+    }
+}
+
+impl<T, const N: usize> StableHash for [T; N]
+where
+    T: StableHash,
+{
+    fn stable_hash(&self, hash: &mut SHA1) {
+        self.as_slice().stable_hash(hash);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> StableHash for Vec<T>
+where
+    T: StableHash,
+{
+    fn stable_hash(&self, hash: &mut SHA1) {
+        self.as_slice().stable_hash(hash);
+    }
+}
+
+impl<T> StableHash for Option<T>
+where
+    T: StableHash,
+{
+    fn stable_hash(&self, hash: &mut SHA1) {
+        match self {
+            None => false.stable_hash(hash),
+            Some(value) => {
+                true.stable_hash(hash);
+                value.stable_hash(hash);
+            },
+        }
+    }
+}
+
+impl<T> StableHash for &T
+where
+    T: StableHash + ?Sized,
+{
+    fn stable_hash(&self, hash: &mut SHA1) {
+        (**self).stable_hash(hash);
+    }
+}
+
+impl<T> StableHash for &mut T
+where
+    T: StableHash + ?Sized,
+{
+    fn stable_hash(&self, hash: &mut SHA1) {
+        (**self).stable_hash(hash);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> StableHash for Box<T>
+where
+    T: StableHash + ?Sized,
+{
+    fn stable_hash(&self, hash: &mut SHA1) {
+        (**self).stable_hash(hash);
+    }
+}
+
+macro_rules! impl_stable_hash_for_tuples {
+    ($(($($name:ident),*)),+) => {
+        $(
+            impl<$($name),*> StableHash for ($($name,)*)
+            where
+                $($name: StableHash,)*
+            {
+                #[allow(non_snake_case, unused_variables)]
+                fn stable_hash(&self, hash: &mut SHA1) {
+                    let ($($name,)*) = self;
+                    $($name.stable_hash(hash);)*
+                }
+            }
+        )+
+    };
+}
+
+impl_stable_hash_for_tuples!(
+    (),
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+    (A, B, C, D, E, F, G),
+    (A, B, C, D, E, F, G, H)
+);
+
+/// The [`core::hash::Hasher`] implementation backed by [`SHA1`].
+///
+/// The integers written to the hasher are encoded the same way as by [`StableHash`], i.e. in little-endian order with `usize` and `isize` widened to 64 bits, and the bytes given to [`write`](core::hash::Hasher::write) are fed as they are.
+/// The [`finish`](core::hash::Hasher::finish) method returns the first 8 bytes of the digest interpreted as a big-endian integer, so its hexadecimal form is a prefix of the hexadecimal digest.
+///
+/// # Example
+///
+/// ```rust
+/// use std::hash::{Hash, Hasher as _};
+///
+/// use chksum_sha1::hasher::Hasher;
+///
+/// let mut hasher = Hasher::new();
+/// "example".hash(&mut hasher);
+/// let digest = hasher.digest();
+/// assert_eq!(
+///     format!("{:016x}", hasher.finish()),
+///     &digest.to_hex_lowercase()[..16]
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Hasher {
+    inner: SHA1,
+}
+
+impl Hasher {
+    /// Creates a new hasher.
+    #[must_use]
+    pub fn new() -> Self {
+        let inner = SHA1::new();
+        Self { inner }
+    }
+
+    /// Produces the full digest of the data written so far.
+    #[must_use]
+    pub fn digest(&self) -> Digest {
+        let Self { inner } = self;
+        inner.digest()
+    }
+
+    /// Unwraps this `Hasher`, returning the underlying hash instance.
+    #[must_use]
+    pub fn into_inner(self) -> SHA1 {
+        let Self { inner } = self;
+        inner
+    }
+}
+
+impl From<SHA1> for Hasher {
+    fn from(inner: SHA1) -> Self {
+        Self { inner }
+    }
+}
+
+impl core::hash::Hasher for Hasher {
+    fn finish(&self) -> u64 {
+        let digest = self.digest().into_inner();
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        u64::from_be_bytes(bytes)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes);
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i8(&mut self, i: i8) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64);
+    }
+}
+
+/// The [`core::hash::BuildHasher`] implementation creating [`Hasher`] instances.
+///
+/// All hashers created by this builder start with the same state, so equal values always produce equal hashes.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashSet;
+///
+/// use chksum_sha1::hasher::BuildHasher;
+///
+/// let mut set = HashSet::with_hasher(BuildHasher);
+/// set.insert("example");
+/// assert!(set.contains("example"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BuildHasher;
+
+impl core::hash::BuildHasher for BuildHasher {
+    type Hasher = Hasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Hasher::new()
+    }
+}
@@ -0,0 +1,58 @@
+//! Hexadecimal encoding, as defined in [RFC 4648](https://www.rfc-editor.org/rfc/rfc4648#section-8).
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The lowercase alphabet.
+pub(crate) const LOWER: &[u8; 16] = b"0123456789abcdef";
+
+/// The uppercase alphabet.
+pub(crate) const UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Encodes the data into the output, which must be twice as long as the data.
+pub(crate) fn encode_into(data: &[u8], alphabet: &[u8; 16], output: &mut [u8]) {
+    debug_assert_eq!(output.len(), data.len() * 2);
+    for (digits, byte) in output.chunks_exact_mut(2).zip(data) {
+        digits[0] = alphabet[usize::from(byte >> 4)];
+        digits[1] = alphabet[usize::from(byte & 0x0F)];
+    }
+}
+
+/// Encodes the data with the given alphabet.
+#[cfg(feature = "alloc")]
+pub(crate) fn encode(data: &[u8], alphabet: &[u8; 16]) -> String {
+    let mut hex = vec![0u8; data.len() * 2];
+    encode_into(data, alphabet, &mut hex);
+    String::from_utf8(hex).expect("hexadecimal digits must be valid UTF-8")
+}
+
+/// Decodes the digits of any case into the output, which must be half as long as the digits.
+///
+/// Returns `None` if any of the digits is invalid.
+#[cfg(feature = "alloc")]
+pub(crate) fn decode_into(hex: &[u8], output: &mut [u8]) -> Option<()> {
+    fn digit(digit: u8) -> Option<u8> {
+        char::from(digit).to_digit(16).map(|digit| digit as u8)
+    }
+
+    debug_assert_eq!(hex.len(), output.len() * 2);
+    for (byte, digits) in output.iter_mut().zip(hex.chunks_exact(2)) {
+        *byte = digit(digits[0])? << 4 | digit(digits[1])?;
+    }
+    Some(())
+}
+
+/// Decodes the digits of any case, returning `None` if they are invalid.
+#[cfg(feature = "alloc")]
+pub(crate) fn decode(hex: &[u8]) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    let mut data = vec![0u8; hex.len() / 2];
+    decode_into(hex, &mut data)?;
+    Some(data)
+}
@@ -0,0 +1,209 @@
+//! This module provides formatting and parsing of the HTTP fields carrying digests of the content.
+//!
+//! Two generations of fields are supported:
+//!
+//! * `Digest` and `Want-Digest` of [RFC 3230](https://www.rfc-editor.org/rfc/rfc3230), where SHA-1 is identified by the `SHA` algorithm, e.g. `Digest: SHA=w0mcJylzCn+AfvuGdqkty2+KP48=`,
+//! * `Repr-Digest`, `Content-Digest`, `Want-Repr-Digest` and `Want-Content-Digest` of [RFC 9530](https://www.rfc-editor.org/rfc/rfc9530), where SHA-1 is identified by the `sha` key, e.g. `Repr-Digest: sha=:w0mcJylzCn+AfvuGdqkty2+KP48=:`.
+//!
+//! A field may list digests of many algorithms, in which case the parsing functions select the SHA-1 one and ignore the others.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_sha1 as sha1;
+//!
+//! let digest = sha1::hash("example data");
+//!
+//! let value = sha1::http::repr_digest_field(digest);
+//! assert_eq!(sha1::http::parse_repr_digest_field(&value), Some(digest));
+//!
+//! let value = format!(
+//!     "MD5=HUXZLQLMuI/KZ5KDcJPcOA==, {}",
+//!     sha1::http::digest_field(digest)
+//! );
+//! assert_eq!(sha1::http::parse_digest_field(&value), Some(digest));
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+
+use crate::{base64, Digest, DIGEST_LENGTH_BYTES};
+
+/// The name of the SHA-1 algorithm in the fields of RFC 3230.
+pub const DIGEST_ALGORITHM: &str = "SHA";
+
+/// The key of the SHA-1 algorithm in the fields of RFC 9530.
+pub const REPR_DIGEST_KEY: &str = "sha";
+
+/// Returns the value of the `Digest` field of RFC 3230 with the digest.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1 as sha1;
+///
+/// let digest = sha1::hash("example");
+/// assert_eq!(
+///     sha1::http::digest_field(digest),
+///     "SHA=w0mcJylzCn+AfvuGdqkty2+KP48="
+/// );
+/// ```
+#[must_use]
+pub fn digest_field(digest: Digest) -> String {
+    format!("{DIGEST_ALGORITHM}={}", base64::encode(digest))
+}
+
+/// Returns the SHA-1 digest listed in the value of the `Digest` field of RFC 3230.
+///
+/// The algorithms are case-insensitive and the digests of other algorithms are ignored.
+/// Returns `None` if the value doesn't list a valid SHA-1 digest.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1 as sha1;
+///
+/// let value =
+///     "sha-256=X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=,sha=w0mcJylzCn+AfvuGdqkty2+KP48=";
+/// assert_eq!(
+///     sha1::http::parse_digest_field(value),
+///     Some(sha1::hash("example"))
+/// );
+/// ```
+#[must_use]
+pub fn parse_digest_field(value: &str) -> Option<Digest> {
+    value.split(',').find_map(|member| {
+        let (algorithm, digest) = member.trim().split_once('=')?;
+        if algorithm.trim().eq_ignore_ascii_case(DIGEST_ALGORITHM) {
+            decode(digest.trim())
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns the quality value of SHA-1 listed in the value of the `Want-Digest` field of RFC 3230.
+///
+/// An algorithm without the quality value has the quality of `1.0`.
+/// Returns `None` if SHA-1 isn't listed, or is listed with the quality of `0.0`, which means that it's not acceptable.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1 as sha1;
+///
+/// assert_eq!(
+///     sha1::http::parse_want_digest_field("SHA-256, SHA;q=0.3"),
+///     Some(0.3)
+/// );
+/// assert_eq!(sha1::http::parse_want_digest_field("sha"), Some(1.0));
+/// assert_eq!(sha1::http::parse_want_digest_field("SHA-256"), None);
+/// ```
+#[must_use]
+pub fn parse_want_digest_field(value: &str) -> Option<f32> {
+    value.split(',').find_map(|member| {
+        let mut parameters = member.split(';').map(str::trim);
+        let algorithm = parameters.next()?;
+        if !algorithm.eq_ignore_ascii_case(DIGEST_ALGORITHM) {
+            return None;
+        }
+        let mut quality = 1.0;
+        for parameter in parameters {
+            if let Some((name, value)) = parameter.split_once('=') {
+                if name.trim().eq_ignore_ascii_case("q") {
+                    quality = value
+                        .trim()
+                        .parse()
+                        .ok()
+                        .filter(|quality| (0.0..=1.0).contains(quality))?;
+                }
+            }
+        }
+        (quality > 0.0).then_some(quality)
+    })
+}
+
+/// Returns the value of the `Repr-Digest` or `Content-Digest` field of RFC 9530 with the digest.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1 as sha1;
+///
+/// let digest = sha1::hash("example");
+/// assert_eq!(
+///     sha1::http::repr_digest_field(digest),
+///     "sha=:w0mcJylzCn+AfvuGdqkty2+KP48=:"
+/// );
+/// ```
+#[must_use]
+pub fn repr_digest_field(digest: Digest) -> String {
+    format!("{REPR_DIGEST_KEY}=:{}:", base64::encode(digest))
+}
+
+/// Returns the SHA-1 digest listed in the value of the `Repr-Digest` or `Content-Digest` field of RFC 9530.
+///
+/// The field is a dictionary of structured field values, so the keys are case-sensitive and the last member with the `sha` key is used.
+/// The digests of other algorithms and the parameters of the members are ignored.
+/// Returns `None` if the value doesn't list a valid SHA-1 digest.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1 as sha1;
+///
+/// let value = "sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:, \
+///              sha=:w0mcJylzCn+AfvuGdqkty2+KP48=:";
+/// assert_eq!(
+///     sha1::http::parse_repr_digest_field(value),
+///     Some(sha1::hash("example"))
+/// );
+/// ```
+#[must_use]
+pub fn parse_repr_digest_field(value: &str) -> Option<Digest> {
+    dictionary_member(value, REPR_DIGEST_KEY)
+        .and_then(|digest| digest.strip_prefix(':')?.strip_suffix(':'))
+        .and_then(decode)
+}
+
+/// Returns the preference of SHA-1 listed in the value of the `Want-Repr-Digest` or `Want-Content-Digest` field of RFC 9530.
+///
+/// The preference ranges from `1` to `10`, the most preferred.
+/// Returns `None` if SHA-1 isn't listed, or is listed with the preference of `0`, which means that it's not acceptable.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1 as sha1;
+///
+/// assert_eq!(
+///     sha1::http::parse_want_repr_digest_field("sha-256=10, sha=3"),
+///     Some(3)
+/// );
+/// assert_eq!(sha1::http::parse_want_repr_digest_field("sha=0"), None);
+/// ```
+#[must_use]
+pub fn parse_want_repr_digest_field(value: &str) -> Option<u8> {
+    dictionary_member(value, REPR_DIGEST_KEY)
+        .and_then(|preference| preference.parse().ok())
+        .filter(|preference| (1..=10).contains(preference))
+}
+
+/// Returns the value of the last member of the structured field dictionary with the key, without parameters.
+///
+/// A member without a value is the Boolean true, i.e. `?1`, which overrides the earlier members like any other value.
+fn dictionary_member<'a>(value: &'a str, key: &str) -> Option<&'a str> {
+    value.split(',').rev().find_map(|member| {
+        let member = member.trim();
+        let member = member.split_once(';').map_or(member, |(member, _)| member);
+        let (name, value) = member.split_once('=').unwrap_or((member, "?1"));
+        (name == key).then_some(value)
+    })
+}
+
+/// Decodes the Base64 digest.
+fn decode(digest: &str) -> Option<Digest> {
+    let digest = base64::decode(digest)?;
+    let digest = <[u8; DIGEST_LENGTH_BYTES]>::try_from(digest).ok()?;
+    Some(Digest::new(digest))
+}
@@ -63,6 +63,27 @@
 //! # }
 //! ```
 //!
+//! ## Chunks
+//!
+//! Use the [`chunk`] module to calculate separate digests of fixed-size chunks of file, directory and so on.
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//!
+//! # use chksum_sha1::Result;
+//! use chksum_sha1 as sha1;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let file = File::open(path)?;
+//! let digests = sha1::chunk::chksum(file, 256 * 1024)?;
+//! for digest in digests {
+//!     println!("{digest}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! # Input Types
 //!
 //! ## Bytes
@@ -237,10 +258,22 @@
 //!
 //! Cargo features are utilized to enable extra options.
 //!
+//! * `std` enables the [`chksum`] function and the [`chunk`] module for files, directories, standard input and so on.
+//! * `alloc` enables the hexadecimal string conversions of the [`Digest`] struct and the [`http`] module with formatting and parsing of HTTP digest fields, the [`sri`] module with formatting and parsing of Subresource Integrity strings, and the multibase strings of the [`multihash`] module.
 //! * `reader` enables the [`reader`] module with the [`Reader`] struct.
 //! * `writer` enables the [`writer`] module with the [`Writer`] struct.
-//!
-//! By default, neither of these features is enabled.
+//! * `torrent` enables the [`torrent`] module with BitTorrent v1 metadata generation and verification.
+//! * `acceleration` enables the hardware-accelerated SHA-1 compression using x86 SHA extensions or ARMv8 cryptographic extensions, detected at runtime.
+//! * `mmap` enables the [`mmap`] module with `unsafe` hashing of memory-mapped files.
+//! * `cache` enables the [`cache`] module with a persistent cache of file digests (Unix only).
+//! * `xattr` enables the [`xattr`] module with storing of digests in extended attributes of files (Linux, Android and Apple platforms only).
+//! * `gzip`, `zstd` and `xz` enable the [`decompress`] module with hashing of decompressed data in the respective formats.
+//! * `tar` enables the [`tar`](mod@tar) module with hashing of entries of tar archives.
+//! * `zip` enables the [`zip`](mod@zip) module with hashing of entries of zip archives and JAR manifest generation and verification.
+//! * `tower` enables the [`tower`](mod@tower) module with a middleware hashing HTTP request and response bodies.
+//! * `digest` implements the [`digest`](https://docs.rs/digest) crate traits for [`SHA1`], e.g. to use it with `hmac`, `pbkdf2` or `rsa` signatures.
+//!
+//! By default, only the `std` feature is enabled.
 //!
 //! To customize your setup, disable the default features and enable only those that you need in your `Cargo.toml` file:
 //!
@@ -261,6 +294,18 @@
 //!
 //! By default, neither of these features is enabled.
 //!
+//! ## No Standard Library
+//!
+//! Disable the default features to use the crate in `no_std` environments.
+//! The [`SHA1`] struct, the [`Digest`] struct and the [`hash()`] function remain available, while the `alloc` feature brings back the hexadecimal string conversions.
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-sha1 = { version = "0.1.0", default-features = false, features = ["alloc"] }
+//! ```
+//!
+//! This configuration builds for the `wasm32-unknown-unknown` target too, with bindings for JavaScript provided by the `chksum-sha1-wasm` crate of the repository.
+//!
 //! # Disclaimer
 //!
 //! The SHA-1 hash function should be used only for backward compatibility due to security issues.
@@ -272,23 +317,87 @@
 //! This crate is licensed under the MIT License.
 
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
-#![forbid(unsafe_code)]
-
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(any(feature = "acceleration", feature = "mmap")), forbid(unsafe_code))]
+#![cfg_attr(any(feature = "acceleration", feature = "mmap"), deny(unsafe_code))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+mod base32;
+#[cfg(feature = "alloc")]
+mod base58;
+#[cfg(feature = "alloc")]
+mod base64;
+pub mod batch;
+#[cfg(all(feature = "cache", unix))]
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod cancel;
+#[cfg(feature = "std")]
+pub mod chunk;
+mod compress;
+#[cfg(any(feature = "gzip", feature = "xz", feature = "zstd"))]
+pub mod decompress;
+pub mod hasher;
+mod hex;
+#[cfg(feature = "alloc")]
+pub mod http;
+#[cfg(feature = "std")]
+pub mod many;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod multihash;
+#[cfg(feature = "std")]
+pub mod path;
+#[cfg(feature = "std")]
+pub mod progress;
 #[cfg(feature = "reader")]
 pub mod reader;
+#[cfg(feature = "digest")]
+mod rustcrypto;
+#[cfg(feature = "alloc")]
+pub mod sri;
+mod state;
+#[cfg(feature = "tar")]
+pub mod tar;
+#[cfg(feature = "torrent")]
+pub mod torrent;
+#[cfg(feature = "tower")]
+pub mod tower;
+pub mod uuid;
+#[cfg(feature = "std")]
+mod walk;
 #[cfg(feature = "writer")]
 pub mod writer;
-
-use std::fmt::{self, Display, Formatter, LowerHex, UpperHex};
-
+#[cfg(all(
+    feature = "xattr",
+    any(target_os = "android", target_os = "linux", target_vendor = "apple")
+))]
+pub mod xattr;
+#[cfg(feature = "zip")]
+pub mod zip;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use ::core::fmt::{self, Display, Formatter, LowerHex, UpperHex};
+use ::core::result;
+#[cfg(feature = "std")]
 use chksum_core as core;
 #[cfg(feature = "async-runtime-tokio")]
 #[doc(no_inline)]
 pub use chksum_core::AsyncChksumable;
+#[cfg(feature = "std")]
 #[doc(no_inline)]
 pub use chksum_core::{Chksumable, Error, Hash, Hashable, Result};
+#[cfg(feature = "std")]
 #[doc(no_inline)]
 pub use chksum_hash_sha1 as hash;
+#[cfg(feature = "digest")]
+#[doc(no_inline)]
+pub use digest;
 
 #[cfg(all(feature = "reader", feature = "async-runtime-tokio"))]
 #[doc(inline)]
@@ -296,6 +405,11 @@ pub use crate::reader::AsyncReader;
 #[cfg(feature = "reader")]
 #[doc(inline)]
 pub use crate::reader::Reader;
+pub use crate::state::{StateError, STATE_LENGTH_BYTES};
+#[doc(inline)]
+pub use crate::uuid::uuid_v5;
+#[cfg(feature = "std")]
+pub use crate::walk::{Operation, PathError};
 #[cfg(all(feature = "writer", feature = "async-runtime-tokio"))]
 #[doc(inline)]
 pub use crate::writer::AsyncWriter;
@@ -303,6 +417,9 @@ pub use crate::writer::AsyncWriter;
 #[doc(inline)]
 pub use crate::writer::Writer;
 
+/// The length of the digest in bytes.
+pub const DIGEST_LENGTH_BYTES: usize = 20;
+
 /// Creates a new hash.
 ///
 /// # Example
@@ -340,7 +457,7 @@ pub fn new() -> SHA1 {
 /// ```
 #[must_use]
 pub fn default() -> SHA1 {
-    core::default()
+    SHA1::default()
 }
 
 /// Computes the hash of the given input.
@@ -357,8 +474,8 @@ pub fn default() -> SHA1 {
 ///     "9fc42adac31303d68b444e6129f13f6093a0e045"
 /// );
 /// ```
-pub fn hash(data: impl core::Hashable) -> Digest {
-    core::hash::<SHA1>(data)
+pub fn hash(data: impl AsRef<[u8]>) -> Digest {
+    SHA1::hash(data)
 }
 
 /// Computes the hash of the given input.
@@ -376,6 +493,7 @@ pub fn hash(data: impl core::Hashable) -> Digest {
 ///     );
 /// }
 /// ```
+#[cfg(feature = "std")]
 pub fn chksum(data: impl core::Chksumable) -> Result<Digest> {
     core::chksum::<SHA1>(data)
 }
@@ -405,7 +523,7 @@ pub async fn async_chksum(data: impl core::AsyncChksumable) -> Result<Digest> {
 /// The SHA-1 hash instance.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct SHA1 {
-    inner: hash::Update,
+    inner: state::State,
 }
 
 impl SHA1 {
@@ -450,7 +568,7 @@ impl SHA1 {
     /// ```
     #[must_use]
     pub fn new() -> Self {
-        let inner = hash::Update::new();
+        let inner = state::State::new();
         Self { inner }
     }
 
@@ -475,7 +593,7 @@ impl SHA1 {
     where
         T: AsRef<[u8]>,
     {
-        self.inner.update(data);
+        self.inner.update(data.as_ref());
     }
 
     /// Resets the hash state to its initial state.
@@ -516,8 +634,72 @@ impl SHA1 {
     pub fn digest(&self) -> Digest {
         self.inner.digest().into()
     }
+
+    /// Exports the hash state into a stable byte format.
+    ///
+    /// The exported state can be stored and later restored with [`SHA1::import_state`], even by another process, to continue hashing where it left off.
+    ///
+    /// # Format
+    ///
+    /// The exported state is always [`STATE_LENGTH_BYTES`] bytes long and consists of the following fields:
+    ///
+    /// | Offset | Length | Description                                                                               |
+    /// |--------|--------|-------------------------------------------------------------------------------------------|
+    /// | 0      | 4      | Magic bytes `sha1`.                                                                       |
+    /// | 4      | 1      | Format version, currently `1`.                                                            |
+    /// | 5      | 20     | Chaining values `H0` to `H4`, each as a big-endian 32-bit integer.                        |
+    /// | 25     | 8      | Total length of the data in bytes, as a big-endian 64-bit integer.                        |
+    /// | 33     | 64     | Buffered tail of the data; only the first `length % 64` bytes are used, the rest is zeroed. |
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha1::SHA1;
+    ///
+    /// let mut hash = SHA1::new();
+    /// hash.update(b"example");
+    /// let state = hash.export_state();
+    ///
+    /// let mut hash = SHA1::import_state(&state).unwrap();
+    /// hash.update(b" data");
+    /// let digest = hash.digest();
+    /// assert_eq!(
+    ///     digest.to_hex_lowercase(),
+    ///     "9fc42adac31303d68b444e6129f13f6093a0e045"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn export_state(&self) -> [u8; STATE_LENGTH_BYTES] {
+        self.inner.export()
+    }
+
+    /// Restores the hash from the state exported by [`SHA1::export_state`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StateError`] if the given bytes are not a valid exported state.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha1::{StateError, SHA1};
+    ///
+    /// let result = SHA1::import_state(b"invalid");
+    /// assert_eq!(
+    ///     result,
+    ///     Err(StateError::InvalidLength {
+    ///         value: 7,
+    ///         proper: 97
+    ///     })
+    /// );
+    /// ```
+    pub fn import_state(state: &[u8]) -> result::Result<Self, StateError> {
+        let inner = state::State::import(state)?;
+        Ok(Self { inner })
+    }
 }
 
+#[cfg(feature = "std")]
 impl core::Hash for SHA1 {
     type Digest = Digest;
 
@@ -538,28 +720,28 @@ impl core::Hash for SHA1 {
 }
 
 /// A hash digest.
-pub struct Digest(hash::Digest);
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Digest([u8; DIGEST_LENGTH_BYTES]);
 
 impl Digest {
     /// Creates a new digest.
     #[must_use]
-    pub const fn new(digest: [u8; hash::DIGEST_LENGTH_BYTES]) -> Self {
-        let inner = hash::Digest::new(digest);
-        Self(inner)
+    pub const fn new(digest: [u8; DIGEST_LENGTH_BYTES]) -> Self {
+        Self(digest)
     }
 
     /// Returns a byte slice of the digest's contents.
     #[must_use]
     pub const fn as_bytes(&self) -> &[u8] {
         let Self(inner) = self;
-        inner.as_bytes()
+        inner
     }
 
     /// Consumes the digest, returning the digest bytes.
     #[must_use]
-    pub fn into_inner(self) -> [u8; hash::DIGEST_LENGTH_BYTES] {
+    pub const fn into_inner(self) -> [u8; DIGEST_LENGTH_BYTES] {
         let Self(inner) = self;
-        inner.into_inner()
+        inner
     }
 
     /// Returns a string in the lowercase hexadecimal representation.
@@ -583,10 +765,10 @@ impl Digest {
     ///     "da39a3ee5e6b4b0d3255bfef95601890afd80709"
     /// );
     /// ```
+    #[cfg(feature = "alloc")]
     #[must_use]
     pub fn to_hex_lowercase(&self) -> String {
-        let Self(inner) = self;
-        inner.to_hex_lowercase()
+        alloc::format!("{self:x}")
     }
 
     /// Returns a string in the uppercase hexadecimal representation.
@@ -610,51 +792,86 @@ impl Digest {
     ///     "DA39A3EE5E6B4B0D3255BFEF95601890AFD80709"
     /// );
     /// ```
+    #[cfg(feature = "alloc")]
     #[must_use]
     pub fn to_hex_uppercase(&self) -> String {
+        alloc::format!("{self:X}")
+    }
+
+    /// Encodes the digest into hexadecimal digits from the given alphabet.
+    fn encode_hex(&self, alphabet: &[u8; 16]) -> [u8; DIGEST_LENGTH_BYTES * 2] {
         let Self(inner) = self;
-        inner.to_hex_uppercase()
+        let mut digits = [0u8; DIGEST_LENGTH_BYTES * 2];
+        hex::encode_into(inner, alphabet, &mut digits);
+        digits
+    }
+
+    /// Decodes the digest from hexadecimal digits of any case, returning `None` if they are invalid.
+    #[cfg(any(
+        all(feature = "cache", unix),
+        feature = "tower",
+        all(
+            feature = "xattr",
+            any(target_os = "android", target_os = "linux", target_vendor = "apple")
+        )
+    ))]
+    pub(crate) fn decode_hex(digits: &[u8]) -> Option<Self> {
+        if digits.len() != DIGEST_LENGTH_BYTES * 2 {
+            return None;
+        }
+        let mut inner = [0u8; DIGEST_LENGTH_BYTES];
+        hex::decode_into(digits, &mut inner)?;
+        Some(Self(inner))
+    }
+
+    /// Writes the hexadecimal digits with respect to the formatter flags.
+    fn fmt_hex(&self, f: &mut Formatter<'_>, alphabet: &[u8; 16], prefix: &str) -> fmt::Result {
+        let hex = self.encode_hex(alphabet);
+        let hex = ::core::str::from_utf8(&hex).expect("hexadecimal digits must be valid UTF-8");
+        if f.alternate() {
+            f.pad_integral(true, prefix, hex)
+        } else {
+            f.pad(hex)
+        }
     }
 }
 
+#[cfg(feature = "std")]
 impl core::Digest for Digest {}
 
 impl AsRef<[u8]> for Digest {
     fn as_ref(&self) -> &[u8] {
-        let Self(inner) = self;
-        inner.as_bytes()
+        self.as_bytes()
     }
 }
 
 impl Display for Digest {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let Self(inner) = self;
-        Display::fmt(inner, f)
+        LowerHex::fmt(self, f)
     }
 }
 
 impl LowerHex for Digest {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let Self(inner) = self;
-        LowerHex::fmt(inner, f)
+        self.fmt_hex(f, hex::LOWER, "0x")
     }
 }
 
 impl UpperHex for Digest {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let Self(inner) = self;
-        UpperHex::fmt(inner, f)
+        self.fmt_hex(f, hex::UPPER, "0X")
     }
 }
 
-impl From<[u8; hash::DIGEST_LENGTH_BYTES]> for Digest {
-    fn from(digest: [u8; hash::DIGEST_LENGTH_BYTES]) -> Self {
+impl From<[u8; DIGEST_LENGTH_BYTES]> for Digest {
+    fn from(digest: [u8; DIGEST_LENGTH_BYTES]) -> Self {
         Self::new(digest)
     }
 }
 
+#[cfg(feature = "std")]
 impl From<hash::Digest> for Digest {
     fn from(digest: hash::Digest) -> Self {
-        Self(digest)
+        Self::new(digest.into_inner())
     }
 }
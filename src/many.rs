@@ -0,0 +1,135 @@
+//! This module provides computation of digests of many inputs concurrently.
+//!
+//! The inputs are hashed independently of each other by at most `concurrency` workers at once: threads for the synchronous interface and Tokio tasks for the asynchronous one.
+//! The results are returned in the order of the inputs, and a failure of one input doesn't affect the others.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use chksum_sha1 as sha1;
+//!
+//! # fn wrapper(paths: &[&Path]) {
+//! let results = sha1::many::chksum(paths.iter().copied(), 4);
+//! for (path, result) in paths.iter().zip(results) {
+//!     match result {
+//!         Ok(digest) => println!("{digest}  {}", path.display()),
+//!         Err(error) => eprintln!("{}: {error}", path.display()),
+//!     }
+//! }
+//! # }
+//! ```
+
+use std::sync::Mutex;
+use std::thread;
+
+use chksum_core as core;
+
+use crate::{Digest, Result, SHA1};
+
+/// Computes the digests of all inputs using at most `concurrency` threads.
+///
+/// The inputs are taken from the iterator lazily, as soon as a thread becomes idle, and a new thread is spawned only while there are inputs left.
+///
+/// # Panics
+///
+/// Panics if `concurrency` is zero, or if computing the digest of any input panics.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::PathBuf;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper(paths: Vec<PathBuf>) {
+/// let results = sha1::many::chksum(paths, 8);
+/// let failures = results.iter().filter(|result| result.is_err()).count();
+/// println!("{failures} inputs failed");
+/// # }
+/// ```
+pub fn chksum<I>(inputs: I, concurrency: usize) -> Vec<Result<Digest>>
+where
+    I: IntoIterator,
+    I::IntoIter: Send,
+    I::Item: core::Chksumable + Send,
+{
+    assert!(concurrency > 0, "concurrency must be greater than zero");
+
+    let inputs = Mutex::new(inputs.into_iter().enumerate());
+    let next = || inputs.lock().unwrap_or_else(|error| error.into_inner()).next();
+    let next = &next;
+    let mut results: Vec<(usize, Result<Digest>)> = thread::scope(|scope| {
+        // every thread is spawned with its first input, so there are never more threads than inputs
+        let mut workers = Vec::new();
+        while workers.len() < concurrency {
+            let Some(first) = next() else {
+                break;
+            };
+            workers.push(scope.spawn(move || {
+                let mut results = Vec::new();
+                let mut input = Some(first);
+                while let Some((index, data)) = input {
+                    results.push((index, core::chksum::<SHA1>(data)));
+                    input = next();
+                }
+                results
+            }));
+        }
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+            .collect()
+    });
+    results.sort_unstable_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Computes the digests of all inputs using at most `concurrency` Tokio tasks.
+///
+/// The tasks are spawned on the current runtime, so the inputs must be owned.
+///
+/// # Panics
+///
+/// Panics if `concurrency` is zero, if computing the digest of any input panics, or if called outside of a Tokio runtime.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::PathBuf;
+/// use chksum_sha1 as sha1;
+///
+/// # async fn wrapper(paths: Vec<PathBuf>) {
+/// let results = sha1::many::async_chksum(paths, 8).await;
+/// let failures = results.iter().filter(|result| result.is_err()).count();
+/// println!("{failures} inputs failed");
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum<I>(inputs: I, concurrency: usize) -> Vec<Result<Digest>>
+where
+    I: IntoIterator,
+    I::Item: core::AsyncChksumable + 'static,
+{
+    use tokio::task::JoinSet;
+
+    assert!(concurrency > 0, "concurrency must be greater than zero");
+
+    let mut inputs = inputs.into_iter().enumerate();
+    let mut tasks = JoinSet::new();
+    let mut results = Vec::new();
+    loop {
+        while tasks.len() < concurrency {
+            let Some((index, input)) = inputs.next() else {
+                break;
+            };
+            tasks.spawn(async move { (index, core::async_chksum::<SHA1>(input).await) });
+        }
+        match tasks.join_next().await {
+            Some(Ok(result)) => results.push(result),
+            Some(Err(error)) => std::panic::resume_unwind(error.into_panic()),
+            None => break,
+        }
+    }
+    results.sort_unstable_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
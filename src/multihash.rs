@@ -0,0 +1,317 @@
+//! This module provides conversion of digests to and from [multihash](https://multiformats.io/multihash/) bytes and [multibase](https://github.com/multiformats/multibase) strings.
+//!
+//! A multihash is the digest prefixed with the varint of the hash function code, `0x11` for SHA-1, and the varint of the digest length, so the multihash of a SHA-1 digest always takes 22 bytes and starts with `0x11 0x14`.
+//! A multibase string is the multihash encoded in a base identified by the leading character, e.g. `f` for lowercase hexadecimal or `z` for Base58 of Bitcoin.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_sha1 as sha1;
+//! # #[cfg(feature = "alloc")]
+//! use chksum_sha1::multihash::Base;
+//!
+//! let digest = sha1::hash("example data");
+//!
+//! let multihash = sha1::multihash::encode(digest);
+//! assert_eq!(multihash[..2], [0x11, 0x14]);
+//! assert_eq!(sha1::multihash::decode(&multihash), Ok(digest));
+//!
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! let multibase = sha1::multihash::encode_multibase(digest, Base::Base58Btc);
+//! assert!(multibase.starts_with('z'));
+//! assert_eq!(sha1::multihash::decode_multibase(&multibase), Ok(digest));
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "alloc")]
+use crate::{base32, base58, base64, hex};
+use crate::{Digest, DIGEST_LENGTH_BYTES};
+
+/// The code of the SHA-1 hash function in the multicodec table.
+pub const CODE: u64 = 0x11;
+
+/// The length of the multihash of a SHA-1 digest in bytes.
+pub const MULTIHASH_LENGTH_BYTES: usize = 2 + DIGEST_LENGTH_BYTES;
+
+/// The maximal length of a varint in bytes, as limited by the multiformats specification.
+const VARINT_MAX_LENGTH_BYTES: usize = 9;
+
+/// An error returned when a multihash or a multibase string cannot be decoded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The varint is truncated, not minimal or too long.
+    InvalidVarint,
+    /// The multihash is of a hash function other than SHA-1.
+    UnsupportedCode(u64),
+    /// The digest has invalid length.
+    InvalidLength {
+        /// The length of the given digest.
+        value: u64,
+        /// The expected length.
+        proper: usize,
+    },
+    /// The multibase string is in an unsupported base.
+    UnsupportedBase(char),
+    /// The multibase string is empty or isn't valid in its base.
+    InvalidEncoding,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidVarint => write!(f, "Invalid multihash varint"),
+            Self::UnsupportedCode(code) => write!(f, "Unsupported multihash code `{code:#x}`"),
+            Self::InvalidLength { value, proper } => {
+                write!(f, "Invalid multihash digest length `{value}`, proper value `{proper}`")
+            },
+            Self::UnsupportedBase(prefix) => write!(f, "Unsupported multibase prefix `{prefix}`"),
+            Self::InvalidEncoding => write!(f, "Invalid multibase encoding"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Returns the multihash of the digest.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1 as sha1;
+///
+/// let digest = sha1::hash("example data");
+/// let multihash = sha1::multihash::encode(digest);
+/// assert_eq!(multihash[..2], [0x11, 0x14]);
+/// assert_eq!(multihash[2..], *digest.as_bytes());
+/// ```
+#[must_use]
+pub fn encode(digest: Digest) -> [u8; MULTIHASH_LENGTH_BYTES] {
+    let mut multihash = [0u8; MULTIHASH_LENGTH_BYTES];
+    // both the code and the length fit in a single byte of varint
+    multihash[0] = CODE as u8;
+    multihash[1] = DIGEST_LENGTH_BYTES as u8;
+    multihash[2..].copy_from_slice(digest.as_bytes());
+    multihash
+}
+
+/// Returns the digest of the SHA-1 multihash.
+///
+/// # Errors
+///
+/// Returns [`Error`] if the bytes aren't a valid multihash, the multihash is of another hash function or has trailing bytes.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1 as sha1;
+/// use chksum_sha1::multihash::Error;
+///
+/// let digest = sha1::hash("example data");
+/// let multihash = sha1::multihash::encode(digest);
+/// assert_eq!(sha1::multihash::decode(&multihash), Ok(digest));
+///
+/// // the SHA2-256 multihash
+/// let multihash = [[0x12, 0x20].as_slice(), &[0u8; 32]].concat();
+/// assert_eq!(
+///     sha1::multihash::decode(&multihash),
+///     Err(Error::UnsupportedCode(0x12))
+/// );
+/// ```
+pub fn decode(multihash: &[u8]) -> Result<Digest, Error> {
+    let (code, multihash) = read_varint(multihash)?;
+    if code != CODE {
+        return Err(Error::UnsupportedCode(code));
+    }
+    let (length, digest) = read_varint(multihash)?;
+    for value in [length, digest.len() as u64] {
+        if value != DIGEST_LENGTH_BYTES as u64 {
+            return Err(Error::InvalidLength {
+                value,
+                proper: DIGEST_LENGTH_BYTES,
+            });
+        }
+    }
+    let mut inner = [0u8; DIGEST_LENGTH_BYTES];
+    inner.copy_from_slice(digest);
+    Ok(Digest::new(inner))
+}
+
+/// Reads the unsigned varint from the start of the bytes, returning the value and the remaining bytes.
+fn read_varint(bytes: &[u8]) -> Result<(u64, &[u8]), Error> {
+    let mut value = 0u64;
+    for (index, &byte) in bytes.iter().take(VARINT_MAX_LENGTH_BYTES).enumerate() {
+        value |= u64::from(byte & 0x7F) << (7 * index);
+        if byte & 0x80 == 0 {
+            // the last byte of a minimal varint is not zero, unless it's the only one
+            if byte == 0 && index > 0 {
+                return Err(Error::InvalidVarint);
+            }
+            return Ok((value, &bytes[index + 1..]));
+        }
+    }
+    Err(Error::InvalidVarint)
+}
+
+/// A base of a multibase string.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Base {
+    /// Lowercase hexadecimal, prefixed with `f`.
+    Base16,
+    /// Uppercase hexadecimal, prefixed with `F`.
+    Base16Upper,
+    /// Lowercase Base32 of RFC 4648 without padding, prefixed with `b`.
+    Base32,
+    /// Uppercase Base32 of RFC 4648 without padding, prefixed with `B`.
+    Base32Upper,
+    /// Base58 with the alphabet of Bitcoin, prefixed with `z`.
+    Base58Btc,
+    /// Base64 of RFC 4648 without padding, prefixed with `m`.
+    Base64,
+    /// Base64 of RFC 4648 with padding, prefixed with `M`.
+    Base64Pad,
+    /// URL and filename safe Base64 of RFC 4648 without padding, prefixed with `u`.
+    Base64Url,
+    /// URL and filename safe Base64 of RFC 4648 with padding, prefixed with `U`.
+    Base64UrlPad,
+}
+
+#[cfg(feature = "alloc")]
+impl Base {
+    /// All supported bases.
+    const ALL: [Self; 9] = [
+        Self::Base16,
+        Self::Base16Upper,
+        Self::Base32,
+        Self::Base32Upper,
+        Self::Base58Btc,
+        Self::Base64,
+        Self::Base64Pad,
+        Self::Base64Url,
+        Self::Base64UrlPad,
+    ];
+
+    /// Returns the base identified by the multibase prefix, or `None` if the base isn't supported.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use chksum_sha1::multihash::Base;
+    ///
+    /// assert_eq!(Base::from_prefix('z'), Some(Base::Base58Btc));
+    /// assert_eq!(Base::from_prefix('k'), None);
+    /// ```
+    #[must_use]
+    pub fn from_prefix(prefix: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|base| base.prefix() == prefix)
+    }
+
+    /// Returns the character which prefixes the multibase strings in this base.
+    #[must_use]
+    pub const fn prefix(self) -> char {
+        match self {
+            Self::Base16 => 'f',
+            Self::Base16Upper => 'F',
+            Self::Base32 => 'b',
+            Self::Base32Upper => 'B',
+            Self::Base58Btc => 'z',
+            Self::Base64 => 'm',
+            Self::Base64Pad => 'M',
+            Self::Base64Url => 'u',
+            Self::Base64UrlPad => 'U',
+        }
+    }
+
+    /// Encodes the data, without the prefix.
+    fn encode(self, data: &[u8]) -> String {
+        match self {
+            Self::Base16 => hex::encode(data, hex::LOWER),
+            Self::Base16Upper => hex::encode(data, hex::UPPER),
+            Self::Base32 => base32::encode(data, base32::LOWER),
+            Self::Base32Upper => base32::encode(data, base32::UPPER),
+            Self::Base58Btc => base58::encode(data),
+            Self::Base64 => base64::STANDARD_NO_PAD.encode(data),
+            Self::Base64Pad => base64::STANDARD.encode(data),
+            Self::Base64Url => base64::URL_SAFE_NO_PAD.encode(data),
+            Self::Base64UrlPad => base64::URL_SAFE.encode(data),
+        }
+    }
+
+    /// Decodes the text, without the prefix.
+    fn decode(self, text: &str) -> Option<Vec<u8>> {
+        match self {
+            // the hexadecimal digits are case-insensitive, while the multibase ones are not
+            Self::Base16 if text.bytes().any(|x| x.is_ascii_uppercase()) => None,
+            Self::Base16Upper if text.bytes().any(|x| x.is_ascii_lowercase()) => None,
+            Self::Base16 | Self::Base16Upper => hex::decode(text.as_bytes()),
+            Self::Base32 => base32::decode(text, base32::LOWER),
+            Self::Base32Upper => base32::decode(text, base32::UPPER),
+            Self::Base58Btc => base58::decode(text),
+            Self::Base64 => base64::STANDARD_NO_PAD.decode(text),
+            Self::Base64Pad => base64::STANDARD.decode(text),
+            Self::Base64Url => base64::URL_SAFE_NO_PAD.decode(text),
+            Self::Base64UrlPad => base64::URL_SAFE.decode(text),
+        }
+    }
+}
+
+/// Returns the multibase string of the multihash of the digest.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1 as sha1;
+/// use chksum_sha1::multihash::Base;
+///
+/// let digest = sha1::hash("example data");
+/// assert_eq!(
+///     sha1::multihash::encode_multibase(digest, Base::Base16),
+///     format!("f1114{digest:x}")
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn encode_multibase(digest: Digest, base: Base) -> String {
+    let mut multibase = String::from(base.prefix());
+    multibase.push_str(&base.encode(&encode(digest)));
+    multibase
+}
+
+/// Returns the digest of the multibase string of the SHA-1 multihash.
+///
+/// # Errors
+///
+/// Returns [`Error`] if the string isn't a valid multibase string in a supported base or doesn't hold a valid SHA-1 multihash.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1 as sha1;
+/// use chksum_sha1::multihash::Error;
+///
+/// let digest =
+///     sha1::multihash::decode_multibase("f1114c3499c2729730a7f807efb8676a92dcb6f8a3f8f")?;
+/// assert_eq!(digest, sha1::hash("example"));
+///
+/// assert_eq!(
+///     sha1::multihash::decode_multibase("k1114"),
+///     Err(Error::UnsupportedBase('k'))
+/// );
+/// # Ok::<(), Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_multibase(multibase: &str) -> Result<Digest, Error> {
+    let mut chars = multibase.chars();
+    let prefix = chars.next().ok_or(Error::InvalidEncoding)?;
+    let base = Base::from_prefix(prefix).ok_or(Error::UnsupportedBase(prefix))?;
+    let multihash = base.decode(chars.as_str()).ok_or(Error::InvalidEncoding)?;
+    decode(&multihash)
+}
@@ -0,0 +1,190 @@
+//! This module provides computation of digests of files and directories with detailed errors.
+//!
+//! Contrary to [`chksum`](crate::chksum), every error is a [`PathError`] which carries the path and the [`Operation`](crate::Operation) that failed, so it's clear which file of a directory couldn't be processed.
+//! The digests are the same as computed by [`chksum`](crate::chksum) for paths.
+//!
+//! The [`chksum_keep_going`] function doesn't stop at the first error, but skips the failed paths and collects all errors instead.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use chksum_sha1 as sha1;
+//!
+//! # fn wrapper(path: &Path) {
+//! match sha1::path::chksum(path) {
+//!     Ok(digest) => println!("{digest}"),
+//!     // e.g. "Cannot open `dir/file.txt`: Permission denied (os error 13)"
+//!     Err(error) => eprintln!("{error}"),
+//! }
+//! # }
+//! ```
+
+use std::path::Path;
+use std::result;
+
+use crate::{walk, Digest, PathError, SHA1};
+
+/// A specialized [`Result`](std::result::Result) type for computations with detailed errors.
+pub type Result<T> = result::Result<T, PathError>;
+
+/// The result of a computation which doesn't stop at errors.
+#[derive(Debug)]
+pub struct Outcome {
+    digest: Digest,
+    errors: Vec<PathError>,
+}
+
+impl Outcome {
+    /// Returns the digest of all files which have been read successfully.
+    #[must_use]
+    pub fn digest(&self) -> Digest {
+        self.digest
+    }
+
+    /// Returns the errors of all paths which have been skipped, in the order of processing.
+    #[must_use]
+    pub fn errors(&self) -> &[PathError] {
+        &self.errors
+    }
+
+    /// Returns `true` if no path has been skipped, so the digest is the same as computed by [`chksum`].
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the digest and the errors.
+    #[must_use]
+    pub fn into_parts(self) -> (Digest, Vec<PathError>) {
+        let Self { digest, errors } = self;
+        (digest, errors)
+    }
+}
+
+/// Computes the hash of the file or directory, stopping at the first error.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use chksum_sha1 as sha1;
+/// use chksum_sha1::Operation;
+///
+/// # fn wrapper(path: &Path) {
+/// if let Err(error) = sha1::path::chksum(path) {
+///     if error.operation() == Operation::Open {
+///         eprintln!("cannot open {}", error.path().display());
+///     }
+/// }
+/// # }
+/// ```
+pub fn chksum(path: impl AsRef<Path>) -> Result<Digest> {
+    let files = walk::files(path.as_ref(), Err)?;
+    let mut hash = SHA1::new();
+    for (file, _) in &files {
+        walk::update(file, &mut hash, |_| Ok(()))?;
+    }
+    Ok(hash.digest())
+}
+
+/// Computes the hash of the file or directory, skipping all paths which cannot be processed.
+///
+/// A file which fails in the middle of reading is skipped entirely, so the digest covers only the files read completely.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper(path: &Path) {
+/// let outcome = sha1::path::chksum_keep_going(path);
+/// for error in outcome.errors() {
+///     eprintln!("{error}");
+/// }
+/// println!("{}", outcome.digest());
+/// # }
+/// ```
+pub fn chksum_keep_going(path: impl AsRef<Path>) -> Outcome {
+    let mut errors = Vec::new();
+    let files = walk::files(path.as_ref(), |error| -> Result<()> {
+        errors.push(error);
+        Ok(())
+    })
+    .unwrap_or_default();
+    let mut hash = SHA1::new();
+    for (file, _) in &files {
+        let mut file_hash = hash.clone();
+        match walk::update(file, &mut file_hash, |_| Ok(())) {
+            Ok(()) => hash = file_hash,
+            Err(error) => errors.push(error),
+        }
+    }
+    let digest = hash.digest();
+    Outcome { digest, errors }
+}
+
+/// Computes the hash of the file or directory, stopping at the first error.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use chksum_sha1 as sha1;
+///
+/// # async fn wrapper(path: &Path) {
+/// match sha1::path::async_chksum(path).await {
+///     Ok(digest) => println!("{digest}"),
+///     Err(error) => eprintln!("{error}"),
+/// }
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum(path: impl AsRef<Path>) -> Result<Digest> {
+    let files = walk::async_files(path.as_ref(), Err).await?;
+    let mut hash = SHA1::new();
+    for (file, _) in &files {
+        walk::async_update(file, &mut hash, |_| Ok(())).await?;
+    }
+    Ok(hash.digest())
+}
+
+/// Computes the hash of the file or directory, skipping all paths which cannot be processed.
+///
+/// A file which fails in the middle of reading is skipped entirely, so the digest covers only the files read completely.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use chksum_sha1 as sha1;
+///
+/// # async fn wrapper(path: &Path) {
+/// let outcome = sha1::path::async_chksum_keep_going(path).await;
+/// for error in outcome.errors() {
+///     eprintln!("{error}");
+/// }
+/// println!("{}", outcome.digest());
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum_keep_going(path: impl AsRef<Path>) -> Outcome {
+    let mut errors = Vec::new();
+    let files = walk::async_files(path.as_ref(), |error| -> Result<()> {
+        errors.push(error);
+        Ok(())
+    })
+    .await
+    .unwrap_or_default();
+    let mut hash = SHA1::new();
+    for (file, _) in &files {
+        let mut file_hash = hash.clone();
+        match walk::async_update(file, &mut file_hash, |_| Ok(())).await {
+            Ok(()) => hash = file_hash,
+            Err(error) => errors.push(error),
+        }
+    }
+    let digest = hash.digest();
+    Outcome { digest, errors }
+}
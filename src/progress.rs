@@ -0,0 +1,199 @@
+//! This module provides computation of digests of files and directories with progress reporting.
+//!
+//! The callback receives a [`Progress`] with the number of bytes processed so far, the total number of bytes when it's known upfront, and the file which is currently processed.
+//! It's called at most once per `interval` while the data is processed, and always once more when the computation is finished.
+//!
+//! The digests are the same as computed by [`chksum`](crate::chksum) for paths.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::time::Duration;
+//!
+//! # use chksum_sha1::Result;
+//! use chksum_sha1 as sha1;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let digest = sha1::progress::chksum(path, Duration::from_millis(100), |progress| {
+//!     if let Some(total) = progress.total() {
+//!         eprint!("\r{}/{total} bytes", progress.processed());
+//!     }
+//! })?;
+//! println!("{digest}");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::{walk, Digest, Error, Result, SHA1};
+
+/// The state of a computation reported to the callback.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Progress<'a> {
+    processed: u64,
+    total: Option<u64>,
+    path: Option<&'a Path>,
+}
+
+impl<'a> Progress<'a> {
+    /// Returns the number of bytes processed so far.
+    #[must_use]
+    pub fn processed(&self) -> u64 {
+        self.processed
+    }
+
+    /// Returns the total number of bytes, if all files are regular files with known lengths.
+    ///
+    /// The lengths are read before the computation, so the number of processed bytes can exceed the total when files grow in the meantime.
+    #[must_use]
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// Returns the path of the file which is currently processed, or `None` if there are no files at all.
+    #[must_use]
+    pub fn path(&self) -> Option<&'a Path> {
+        self.path
+    }
+}
+
+/// Computes the hash of the file or directory, calling `callback` at most once per `interval` and once at the end.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::time::Duration;
+///
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let digest = sha1::progress::chksum(path, Duration::from_secs(1), |progress| {
+///     if let Some(path) = progress.path() {
+///         eprintln!(
+///             "{} bytes, processing {}",
+///             progress.processed(),
+///             path.display()
+///         );
+///     }
+/// })?;
+/// println!("{digest}");
+/// # Ok(())
+/// # }
+/// ```
+pub fn chksum<F>(path: impl AsRef<Path>, interval: Duration, callback: F) -> Result<Digest>
+where
+    F: FnMut(&Progress<'_>),
+{
+    let files = walk::files(path.as_ref(), |error| Err(Error::from(error)))?;
+    let mut reporter = Reporter::new(interval, total(&files), callback);
+    let mut hash = SHA1::new();
+    for (file, _) in &files {
+        walk::update(file, &mut hash, |length| -> Result<()> {
+            reporter.update(file, length);
+            Ok(())
+        })?;
+    }
+    reporter.finish(files.last().map(|(file, _)| file.as_path()));
+    Ok(hash.digest())
+}
+
+/// Computes the hash of the file or directory, calling `callback` at most once per `interval` and once at the end.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::time::Duration;
+///
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # async fn wrapper(path: &Path) -> Result<()> {
+/// let digest = sha1::progress::async_chksum(path, Duration::from_secs(1), |progress| {
+///     eprintln!("{} bytes", progress.processed());
+/// })
+/// .await?;
+/// println!("{digest}");
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_chksum<F>(path: impl AsRef<Path>, interval: Duration, callback: F) -> Result<Digest>
+where
+    F: FnMut(&Progress<'_>),
+{
+    let files = walk::async_files(path.as_ref(), |error| Err(Error::from(error))).await?;
+    let mut reporter = Reporter::new(interval, total(&files), callback);
+    let mut hash = SHA1::new();
+    for (file, _) in &files {
+        walk::async_update(file, &mut hash, |length| -> Result<()> {
+            reporter.update(file, length);
+            Ok(())
+        })
+        .await?;
+    }
+    reporter.finish(files.last().map(|(file, _)| file.as_path()));
+    Ok(hash.digest())
+}
+
+/// Returns the total length of the files, if all of them are regular files.
+fn total(files: &[(PathBuf, Metadata)]) -> Option<u64> {
+    files
+        .iter()
+        .map(|(_, metadata)| metadata.is_file().then_some(metadata.len()))
+        .sum()
+}
+
+/// Throttles the calls of the callback.
+struct Reporter<F> {
+    interval: Duration,
+    reported: Instant,
+    processed: u64,
+    total: Option<u64>,
+    callback: F,
+}
+
+impl<F> Reporter<F>
+where
+    F: FnMut(&Progress<'_>),
+{
+    fn new(interval: Duration, total: Option<u64>, callback: F) -> Self {
+        let reported = Instant::now();
+        Self {
+            interval,
+            reported,
+            processed: 0,
+            total,
+            callback,
+        }
+    }
+
+    /// Accounts the processed chunk of the file and calls the callback if the interval has elapsed.
+    fn update(&mut self, path: &Path, length: usize) {
+        self.processed += length as u64;
+        if self.reported.elapsed() >= self.interval {
+            self.report(Some(path));
+            self.reported = Instant::now();
+        }
+    }
+
+    /// Calls the callback with the final state.
+    fn finish(&mut self, path: Option<&Path>) {
+        self.report(path);
+    }
+
+    fn report(&mut self, path: Option<&Path>) {
+        let progress = Progress {
+            processed: self.processed,
+            total: self.total,
+            path,
+        };
+        (self.callback)(&progress);
+    }
+}
@@ -0,0 +1,50 @@
+//! Implementations of the [`digest`] crate traits for [`SHA1`].
+//!
+//! This module is optional and can be enabled using the `digest` Cargo feature.
+
+use digest::const_oid::{AssociatedOid, ObjectIdentifier};
+use digest::consts::{U20, U64};
+use digest::core_api::BlockSizeUser;
+use digest::{FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset, Update};
+
+use crate::SHA1;
+
+impl HashMarker for SHA1 {}
+
+/// The object identifier of SHA-1, `id-sha1` of [RFC 3279](https://www.rfc-editor.org/rfc/rfc3279#section-2.2.1), used e.g. by PKCS #1 signatures and X.509 certificates.
+impl AssociatedOid for SHA1 {
+    const OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.14.3.2.26");
+}
+
+impl OutputSizeUser for SHA1 {
+    type OutputSize = U20;
+}
+
+impl BlockSizeUser for SHA1 {
+    type BlockSize = U64;
+}
+
+impl Update for SHA1 {
+    fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+}
+
+impl FixedOutput for SHA1 {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.inner.digest());
+    }
+}
+
+impl FixedOutputReset for SHA1 {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.inner.digest());
+        self.inner.reset();
+    }
+}
+
+impl Reset for SHA1 {
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
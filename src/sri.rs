@@ -0,0 +1,129 @@
+//! This module provides formatting and parsing of [Subresource Integrity](https://www.w3.org/TR/SRI/) strings, e.g. `sha1-w0mcJylzCn+AfvuGdqkty2+KP48=`.
+//!
+//! Integrity strings are used by the `integrity` attribute of HTML elements, as well as by npm lockfiles and package metadata, which still carry SHA-1 integrity strings for older packages.
+//! An integrity string may list digests of many algorithms separated by whitespace, in which case the parsing functions select the SHA-1 one and ignore the others.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_sha1 as sha1;
+//!
+//! let digest = sha1::hash("example");
+//!
+//! let integrity = sha1::sri::integrity(digest);
+//! assert_eq!(integrity, "sha1-w0mcJylzCn+AfvuGdqkty2+KP48=");
+//!
+//! let integrity = format!("sha256-UNhY4JhezH9gQYqvDMWrWH9CwlcKiECVqejMrND2VFw= {integrity}");
+//! assert_eq!(sha1::sri::parse_integrity(&integrity), Some(digest));
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::io::{self, ErrorKind};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+#[cfg(feature = "std")]
+use crate::Result;
+use crate::{base64, Digest, DIGEST_LENGTH_BYTES};
+
+/// The name of the SHA-1 algorithm in integrity strings.
+pub const ALGORITHM: &str = "sha1";
+
+/// Returns the integrity string of the digest.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1 as sha1;
+///
+/// let digest = sha1::hash("example");
+/// assert_eq!(
+///     sha1::sri::integrity(digest),
+///     "sha1-w0mcJylzCn+AfvuGdqkty2+KP48="
+/// );
+/// ```
+#[must_use]
+pub fn integrity(digest: Digest) -> String {
+    format!("{ALGORITHM}-{}", base64::encode(digest))
+}
+
+/// Returns the SHA-1 digest listed in the integrity string.
+///
+/// The algorithms are case-insensitive, while the options following `?` and the digests of other algorithms are ignored.
+/// If many SHA-1 digests are listed, the first valid one is returned.
+/// Returns `None` if the string doesn't list a valid SHA-1 digest.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1 as sha1;
+///
+/// let integrity =
+///     "sha256-UNhY4JhezH9gQYqvDMWrWH9CwlcKiECVqejMrND2VFw= sha1-w0mcJylzCn+AfvuGdqkty2+KP48=";
+/// assert_eq!(
+///     sha1::sri::parse_integrity(integrity),
+///     Some(sha1::hash("example"))
+/// );
+/// assert_eq!(
+///     sha1::sri::parse_integrity("sha256-UNhY4JhezH9gQYqvDMWrWH9CwlcKiECVqejMrND2VFw="),
+///     None
+/// );
+/// ```
+#[must_use]
+pub fn parse_integrity(integrity: &str) -> Option<Digest> {
+    digests(integrity).next()
+}
+
+/// Verifies the file, e.g. a package tarball, against the SHA-1 digests listed in the integrity string.
+///
+/// Returns `true` if the digest of the file matches any of the listed SHA-1 digests.
+///
+/// # Errors
+///
+/// Returns [`Error`](crate::Error) if the integrity string doesn't list a valid SHA-1 digest, or if the file cannot be read.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let integrity = "sha1-w0mcJylzCn+AfvuGdqkty2+KP48=";
+/// if !sha1::sri::verify(path, integrity)? {
+///     eprintln!("integrity check failed for {}", path.display());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn verify(path: impl AsRef<Path>, integrity: &str) -> Result<bool> {
+    if parse_integrity(integrity).is_none() {
+        return Err(io::Error::new(ErrorKind::InvalidInput, "missing SHA-1 integrity").into());
+    }
+    let digest = crate::chksum(path.as_ref())?;
+    Ok(digests(integrity).any(|expected| expected == digest))
+}
+
+/// Returns the valid SHA-1 digests listed in the integrity string.
+fn digests(integrity: &str) -> impl Iterator<Item = Digest> + '_ {
+    integrity.split_ascii_whitespace().filter_map(|metadata| {
+        let metadata = metadata.split_once('?').map_or(metadata, |(metadata, _)| metadata);
+        let (algorithm, digest) = metadata.split_once('-')?;
+        if algorithm.eq_ignore_ascii_case(ALGORITHM) {
+            decode(digest)
+        } else {
+            None
+        }
+    })
+}
+
+/// Decodes the Base64 digest.
+fn decode(digest: &str) -> Option<Digest> {
+    let digest = base64::decode(digest)?;
+    let digest = <[u8; DIGEST_LENGTH_BYTES]>::try_from(digest).ok()?;
+    Some(Digest::new(digest))
+}
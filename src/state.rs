@@ -0,0 +1,197 @@
+//! The streaming state of the SHA-1 hash function.
+
+use core::fmt::{self, Debug, Display, Formatter};
+
+use crate::compress::{compress, BLOCK_LENGTH_BYTES};
+use crate::DIGEST_LENGTH_BYTES;
+
+/// The length of the exported state in bytes.
+pub const STATE_LENGTH_BYTES: usize = 97;
+
+const MAGIC: [u8; 4] = *b"sha1";
+const VERSION: u8 = 1;
+
+/// The initial chaining values.
+#[allow(clippy::unreadable_literal)]
+pub(crate) const H: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+/// The streaming state: chaining values, buffered tail of the data and total length.
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct State {
+    chaining: [u32; 5],
+    /// Bytes behind the buffered tail are always zeroed.
+    unprocessed: [u8; BLOCK_LENGTH_BYTES],
+    length: u64,
+}
+
+impl State {
+    pub(crate) const fn new() -> Self {
+        Self {
+            chaining: H,
+            unprocessed: [0u8; BLOCK_LENGTH_BYTES],
+            length: 0,
+        }
+    }
+
+    /// Returns the number of buffered bytes.
+    const fn unprocessed_length(&self) -> usize {
+        (self.length % BLOCK_LENGTH_BYTES as u64) as usize
+    }
+
+    pub(crate) fn update(&mut self, mut data: &[u8]) {
+        let unprocessed = self.unprocessed_length();
+        self.length = self.length.wrapping_add(data.len() as u64);
+
+        if unprocessed > 0 {
+            let missing = BLOCK_LENGTH_BYTES - unprocessed;
+            if data.len() < missing {
+                self.unprocessed[unprocessed..unprocessed + data.len()].copy_from_slice(data);
+                return;
+            }
+            let (fillment, rest) = data.split_at(missing);
+            self.unprocessed[unprocessed..].copy_from_slice(fillment);
+            let block = self.unprocessed;
+            compress(&mut self.chaining, &block);
+            data = rest;
+        }
+
+        let (blocks, remainder) = data.split_at(data.len() - data.len() % BLOCK_LENGTH_BYTES);
+        compress(&mut self.chaining, blocks);
+        self.unprocessed[..remainder.len()].copy_from_slice(remainder);
+        self.unprocessed[remainder.len()..].fill(0);
+    }
+
+    pub(crate) fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub(crate) fn digest(&self) -> [u8; DIGEST_LENGTH_BYTES] {
+        let mut chaining = self.chaining;
+        let (blocks, length) = pad(&self.unprocessed[..self.unprocessed_length()], self.length);
+        compress(&mut chaining, &blocks[..length]);
+        encode(chaining)
+    }
+
+    pub(crate) fn export(&self) -> [u8; STATE_LENGTH_BYTES] {
+        let mut state = [0u8; STATE_LENGTH_BYTES];
+        state[0..4].copy_from_slice(&MAGIC);
+        state[4] = VERSION;
+        for (bytes, value) in state[5..25].chunks_exact_mut(4).zip(self.chaining) {
+            bytes.copy_from_slice(&value.to_be_bytes());
+        }
+        state[25..33].copy_from_slice(&self.length.to_be_bytes());
+        let unprocessed = self.unprocessed_length();
+        state[33..33 + unprocessed].copy_from_slice(&self.unprocessed[..unprocessed]);
+        state
+    }
+
+    pub(crate) fn import(state: &[u8]) -> Result<Self, StateError> {
+        if state.len() != STATE_LENGTH_BYTES {
+            return Err(StateError::InvalidLength {
+                value: state.len(),
+                proper: STATE_LENGTH_BYTES,
+            });
+        }
+        if state[0..4] != MAGIC {
+            return Err(StateError::InvalidMagic);
+        }
+        if state[4] != VERSION {
+            return Err(StateError::UnsupportedVersion(state[4]));
+        }
+        let mut chaining = [0u32; 5];
+        for (value, bytes) in chaining.iter_mut().zip(state[5..25].chunks_exact(4)) {
+            *value = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        let length = u64::from_be_bytes(
+            state[25..33]
+                .try_into()
+                .expect("slice length must be exact size as u64"),
+        );
+        let mut unprocessed = [0u8; BLOCK_LENGTH_BYTES];
+        unprocessed.copy_from_slice(&state[33..]);
+        let state = Self {
+            chaining,
+            unprocessed,
+            length,
+        };
+        if unprocessed[state.unprocessed_length()..].iter().any(|&byte| byte != 0) {
+            return Err(StateError::InvalidPadding);
+        }
+        Ok(state)
+    }
+}
+
+/// Returns the final blocks made of the unprocessed tail of the data and the padding, together with their length.
+pub(crate) fn pad(unprocessed: &[u8], length: u64) -> ([u8; 2 * BLOCK_LENGTH_BYTES], usize) {
+    let length = length.wrapping_mul(8).to_be_bytes(); // convert byte-length into bits-length
+
+    let mut blocks = [0u8; 2 * BLOCK_LENGTH_BYTES];
+    blocks[..unprocessed.len()].copy_from_slice(unprocessed);
+    blocks[unprocessed.len()] = 0x80;
+    let blocks_length = if unprocessed.len() + 1 + length.len() > BLOCK_LENGTH_BYTES {
+        2 * BLOCK_LENGTH_BYTES
+    } else {
+        BLOCK_LENGTH_BYTES
+    };
+    blocks[blocks_length - length.len()..blocks_length].copy_from_slice(&length);
+    (blocks, blocks_length)
+}
+
+/// Converts the final chaining values into the digest.
+pub(crate) fn encode(chaining: [u32; 5]) -> [u8; DIGEST_LENGTH_BYTES] {
+    let mut digest = [0u8; DIGEST_LENGTH_BYTES];
+    for (bytes, value) in digest.chunks_exact_mut(4).zip(chaining) {
+        bytes.copy_from_slice(&value.to_be_bytes());
+    }
+    digest
+}
+
+impl Debug for State {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("State")
+            .field("chaining", &self.chaining)
+            .field("unprocessed", &&self.unprocessed[..self.unprocessed_length()])
+            .field("length", &self.length)
+            .finish()
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An error returned when the exported state cannot be imported.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StateError {
+    /// The state has invalid length.
+    InvalidLength {
+        /// The length of the given state.
+        value: usize,
+        /// The expected length.
+        proper: usize,
+    },
+    /// The state doesn't start with the magic bytes.
+    InvalidMagic,
+    /// The state was exported in an unsupported format version.
+    UnsupportedVersion(u8),
+    /// The unused part of the buffered tail is not zeroed.
+    InvalidPadding,
+}
+
+impl Display for StateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength { value, proper } => {
+                write!(f, "Invalid state length `{value}`, proper value `{proper}`")
+            },
+            Self::InvalidMagic => write!(f, "Invalid state magic bytes"),
+            Self::UnsupportedVersion(version) => write!(f, "Unsupported state version `{version}`"),
+            Self::InvalidPadding => write!(f, "Invalid state padding"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StateError {}
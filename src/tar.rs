@@ -0,0 +1,307 @@
+//! This module provides computation of digests of entries of tar archives, without extracting them.
+//!
+//! The archive is read as a stream, so it may come from a file, a pipe or a decompressor.
+//! For every entry, its path, type, size, permissions and the [`Digest`] of its data are collected into an [`Entry`].
+//!
+//! # Canonical Digest
+//!
+//! Two archives with the same contents rarely have the same bytes, because of modification times, owners and header encodings.
+//! The [`canonical_digest`] of the entries depends only on their order, paths, types, permissions, link targets, sizes and data, so it can be used to compare archives produced by reproducible builds.
+//!
+//! # Enabling
+//!
+//! This module is optional and can be enabled using the `tar` Cargo feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//!
+//! # use chksum_sha1::Result;
+//! use chksum_sha1 as sha1;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let entries = sha1::tar::entries(File::open(path)?)?;
+//! for entry in &entries {
+//!     println!("{}  {}", entry.digest(), entry.path().display());
+//! }
+//! println!("{}", sha1::tar::canonical_digest(&entries));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::{Digest, Result, SHA1};
+
+/// The type of an entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// A regular file.
+    File,
+    /// A directory.
+    Directory,
+    /// A symbolic link.
+    Symlink,
+    /// A hard link to another entry.
+    HardLink,
+    /// A character device.
+    CharacterDevice,
+    /// A block device.
+    BlockDevice,
+    /// A named pipe.
+    Fifo,
+    /// Any other type, with its type flag.
+    Other(u8),
+}
+
+impl Kind {
+    fn new(kind: ::tar::EntryType) -> Self {
+        match kind {
+            ::tar::EntryType::Regular | ::tar::EntryType::Continuous => Self::File,
+            ::tar::EntryType::Directory => Self::Directory,
+            ::tar::EntryType::Symlink => Self::Symlink,
+            ::tar::EntryType::Link => Self::HardLink,
+            ::tar::EntryType::Char => Self::CharacterDevice,
+            ::tar::EntryType::Block => Self::BlockDevice,
+            ::tar::EntryType::Fifo => Self::Fifo,
+            kind => Self::Other(kind.as_byte()),
+        }
+    }
+
+    /// Returns the type flag of the POSIX tar format.
+    fn flag(self) -> u8 {
+        match self {
+            Self::File => b'0',
+            Self::HardLink => b'1',
+            Self::Symlink => b'2',
+            Self::CharacterDevice => b'3',
+            Self::BlockDevice => b'4',
+            Self::Directory => b'5',
+            Self::Fifo => b'6',
+            Self::Other(flag) => flag,
+        }
+    }
+}
+
+/// An entry of the archive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    path: PathBuf,
+    kind: Kind,
+    size: u64,
+    mode: u32,
+    link: Option<PathBuf>,
+    digest: Digest,
+}
+
+impl Entry {
+    /// Returns the path of the entry.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the type of the entry.
+    #[must_use]
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Returns the size of the data of the entry.
+    #[must_use]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the permission bits of the entry.
+    #[must_use]
+    pub fn mode(&self) -> u32 {
+        self.mode
+    }
+
+    /// Returns the target of a symbolic or hard link.
+    #[must_use]
+    pub fn link(&self) -> Option<&Path> {
+        self.link.as_deref()
+    }
+
+    /// Returns the digest of the data of the entry.
+    #[must_use]
+    pub fn digest(&self) -> Digest {
+        self.digest
+    }
+}
+
+/// Reads the archive and computes the digests of all its entries, in the order of the archive.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::File;
+///
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+/// use chksum_sha1::tar::Kind;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let entries = sha1::tar::entries(File::open(path)?)?;
+/// for entry in entries.iter().filter(|entry| entry.kind() == Kind::File) {
+///     println!("{}  {}", entry.digest(), entry.path().display());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn entries(reader: impl Read) -> Result<Vec<Entry>> {
+    let mut archive = ::tar::Archive::new(reader);
+    let mut entries = Vec::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let kind = entry.header().entry_type();
+        // the global extended headers describe the archive, not an entry
+        if kind.is_pax_global_extensions() {
+            continue;
+        }
+        let path = entry.path()?.into_owned();
+        let link = entry.link_name()?.map(|link| link.into_owned());
+        let mode = entry.header().mode()? & 0o7777;
+        let size = entry.size();
+
+        let mut hash = SHA1::new();
+        let mut buffer = [0u8; 8 * 1024];
+        loop {
+            let length = entry.read(&mut buffer)?;
+            if length == 0 {
+                break;
+            }
+            hash.update(&buffer[..length]);
+        }
+        let digest = hash.digest();
+
+        entries.push(Entry {
+            path,
+            kind: Kind::new(kind),
+            size,
+            mode,
+            link,
+            digest,
+        });
+    }
+    Ok(entries)
+}
+
+/// Reads the archive and computes the digests of all its entries, in the order of the archive.
+///
+/// The archive is parsed on a blocking thread of the current Tokio runtime.
+///
+/// # Panics
+///
+/// Panics if called outside of a Tokio runtime.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+/// use tokio::fs::File;
+///
+/// # async fn wrapper(path: &Path) -> Result<()> {
+/// let entries = sha1::tar::async_entries(File::open(path).await?).await?;
+/// println!("{}", sha1::tar::canonical_digest(&entries));
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "async-runtime-tokio")]
+pub async fn async_entries<R>(reader: R) -> Result<Vec<Entry>>
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    let reader = bridge::Bridge::new(reader);
+    tokio::task::spawn_blocking(move || entries(reader))
+        .await
+        .unwrap_or_else(|error| std::panic::resume_unwind(error.into_panic()))
+}
+
+/// Computes the digest of the entries which doesn't depend on modification times, owners and header encodings.
+///
+/// For every entry, the following fields are hashed in order:
+///
+/// * the POSIX type flag (1 byte),
+/// * the permission bits (4 bytes, big-endian),
+/// * the length of the path (8 bytes, big-endian) followed by the path,
+/// * the length of the link target (8 bytes, big-endian) followed by the link target, empty if there is none,
+/// * the size of the data (8 bytes, big-endian),
+/// * the digest of the data (20 bytes).
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::File;
+///
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper(first: &Path, second: &Path) -> Result<()> {
+/// let first = sha1::tar::entries(File::open(first)?)?;
+/// let second = sha1::tar::entries(File::open(second)?)?;
+/// if sha1::tar::canonical_digest(&first) == sha1::tar::canonical_digest(&second) {
+///     println!("the archives have the same contents");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub fn canonical_digest(entries: &[Entry]) -> Digest {
+    fn update_bytes(hash: &mut SHA1, bytes: &[u8]) {
+        hash.update((bytes.len() as u64).to_be_bytes());
+        hash.update(bytes);
+    }
+
+    let mut hash = SHA1::new();
+    for entry in entries {
+        hash.update([entry.kind.flag()]);
+        hash.update(entry.mode.to_be_bytes());
+        update_bytes(&mut hash, entry.path.as_os_str().as_encoded_bytes());
+        let link = entry.link.as_deref().unwrap_or_else(|| Path::new(""));
+        update_bytes(&mut hash, link.as_os_str().as_encoded_bytes());
+        hash.update(entry.size.to_be_bytes());
+        hash.update(entry.digest);
+    }
+    hash.digest()
+}
+
+#[cfg(feature = "async-runtime-tokio")]
+mod bridge {
+    use std::io::{self, Read};
+
+    use tokio::io::{AsyncRead, AsyncReadExt};
+    use tokio::runtime::Handle;
+
+    /// A synchronous reader of an asynchronous one, usable only on blocking threads of the runtime.
+    pub(super) struct Bridge<R> {
+        reader: R,
+        handle: Handle,
+    }
+
+    impl<R> Bridge<R> {
+        pub(super) fn new(reader: R) -> Self {
+            let handle = Handle::current();
+            Self { reader, handle }
+        }
+    }
+
+    impl<R> Read for Bridge<R>
+    where
+        R: AsyncRead + Unpin,
+    {
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            let Self { reader, handle } = self;
+            handle.block_on(reader.read(buffer))
+        }
+    }
+}
@@ -0,0 +1,541 @@
+//! This module is optional and can be enabled using the `torrent` Cargo feature.
+//!
+//! The module allows to generate BitTorrent v1 metadata (the `info` dictionary, the `pieces` string and the info-hash) for a file or a directory, and to verify on-disk data against an existing `.torrent` file.
+//!
+//! # Enabling
+//!
+//! Add the following entry to your `Cargo.toml` file to enable the `torrent` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! chksum-sha1 = { version = "0.1.0", features = ["torrent"] }
+//! ```
+//!
+//! Alternatively, use the [`cargo add`](https://doc.rust-lang.org/cargo/commands/cargo-add.html) subcommand:
+//!
+//! ```shell
+//! cargo add chksum-sha1 --features torrent
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! # use chksum_sha1::Result;
+//! use chksum_sha1 as sha1;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let info = sha1::torrent::info(path, 256 * 1024)?;
+//! let encoded = info.to_bencode();
+//! let info_hash = info.info_hash();
+//! println!("{info_hash}");
+//!
+//! let verification = info.verify(path)?;
+//! assert!(verification.is_valid());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Layout
+//!
+//! For a directory, files are listed in the same order in which [`chksum`](crate::chksum) processes them (entries sorted by path, subdirectories visited recursively), so the concatenated data of the pieces is identical to the data hashed by [`chksum`](crate::chksum).
+//! Empty directories are not represented in the metadata.
+
+mod bencode;
+
+use std::fs::{read_dir, File};
+use std::io::{self, ErrorKind, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::chunk::Chunker;
+use crate::{hash, Digest, Result, SHA1};
+
+/// The size of the buffer used to read files.
+const BUFFER_LENGTH: usize = 64 * 1024;
+
+/// Creates [`Info`] for the given file or directory.
+///
+/// # Panics
+///
+/// Panics if `piece_length` is zero.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let info = sha1::torrent::info(path, 256 * 1024)?;
+/// println!("{}", info.info_hash());
+/// # Ok(())
+/// # }
+/// ```
+pub fn info(path: impl AsRef<Path>, piece_length: usize) -> Result<Info> {
+    Info::new(path, piece_length)
+}
+
+/// Computes the info-hash of the existing `.torrent` file.
+///
+/// The digest is calculated over the `info` dictionary exactly as it is encoded in the file, so the keys unknown to this module are taken into account as well.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::read;
+///
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let torrent = read(path)?;
+/// let info_hash = sha1::torrent::info_hash(&torrent)?;
+/// println!("{info_hash}");
+/// # Ok(())
+/// # }
+/// ```
+pub fn info_hash(torrent: &[u8]) -> Result<Digest> {
+    let torrent = bencode::decode(torrent)?;
+    let info = torrent.get(b"info").ok_or_else(|| invalid("missing info dictionary"))?;
+    Ok(SHA1::hash(info.raw))
+}
+
+/// Verifies the data located at `path` against the existing `.torrent` file.
+///
+/// For a single-file torrent `path` points to the file itself, otherwise it points to the directory which contains the files listed in the torrent.
+/// Missing or truncated files don't cause an error, the pieces which cover them are reported as invalid instead.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::read;
+///
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper(torrent: &Path, path: &Path) -> Result<()> {
+/// let torrent = read(torrent)?;
+/// let verification = sha1::torrent::verify(&torrent, path)?;
+/// for piece in verification.invalid_pieces() {
+///     println!("piece {piece} is invalid");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify(torrent: &[u8], path: impl AsRef<Path>) -> Result<Verification> {
+    let info = Info::from_torrent(torrent)?;
+    info.verify(path)
+}
+
+/// The `info` dictionary of a BitTorrent v1 metainfo file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Info {
+    name: String,
+    piece_length: usize,
+    files: Files,
+    pieces: Vec<Digest>,
+}
+
+/// The files described by [`Info`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Files {
+    /// A single file with the given length, named after [`Info::name`].
+    Single {
+        /// The length of the file in bytes.
+        length: u64,
+    },
+    /// Multiple files stored in a directory named after [`Info::name`].
+    Multiple(Vec<FileInfo>),
+}
+
+/// A file entry of a multi-file torrent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileInfo {
+    path: Vec<String>,
+    length: u64,
+}
+
+impl FileInfo {
+    /// Returns the path components of the file relative to the torrent directory.
+    #[must_use]
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// Returns the length of the file in bytes.
+    #[must_use]
+    pub const fn length(&self) -> u64 {
+        self.length
+    }
+}
+
+impl Info {
+    /// Creates [`Info`] for the given file or directory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `piece_length` is zero.
+    pub fn new(path: impl AsRef<Path>, piece_length: usize) -> Result<Self> {
+        let path = path.as_ref();
+        let name = match path.file_name() {
+            Some(_) => utf8_name(path)?,
+            // paths like `.` have no file name
+            None => utf8_name(&path.canonicalize()?)?,
+        };
+        let mut chunker = Chunker::new(piece_length);
+        let mut pieces = Vec::new();
+        let files = if path.metadata()?.is_dir() {
+            let mut entries = Vec::new();
+            walk(path, &[], &mut entries)?;
+            let mut files = Vec::with_capacity(entries.len());
+            for (file, path) in entries {
+                let length = hash_file(&file, &mut chunker, &mut pieces)?;
+                files.push(FileInfo { path, length });
+            }
+            Files::Multiple(files)
+        } else {
+            let length = hash_file(path, &mut chunker, &mut pieces)?;
+            Files::Single { length }
+        };
+        pieces.extend(chunker.finish());
+        Ok(Self {
+            name,
+            piece_length,
+            files,
+            pieces,
+        })
+    }
+
+    /// Parses [`Info`] from the existing `.torrent` file.
+    pub fn from_torrent(torrent: &[u8]) -> Result<Self> {
+        let torrent = bencode::decode(torrent)?;
+        let info = &torrent
+            .get(b"info")
+            .ok_or_else(|| invalid("missing info dictionary"))?
+            .value;
+
+        let name = info
+            .get(b"name")
+            .and_then(|entry| entry.value.as_bytes())
+            .ok_or_else(|| invalid("missing name"))?;
+        let name = utf8_component(name)?;
+
+        let piece_length = info
+            .get(b"piece length")
+            .and_then(|entry| entry.value.as_integer())
+            .and_then(|piece_length| usize::try_from(piece_length).ok())
+            .filter(|&piece_length| piece_length > 0)
+            .ok_or_else(|| invalid("missing or invalid piece length"))?;
+
+        let pieces = info
+            .get(b"pieces")
+            .and_then(|entry| entry.value.as_bytes())
+            .ok_or_else(|| invalid("missing pieces"))?;
+        let chunks = pieces.chunks_exact(hash::DIGEST_LENGTH_BYTES);
+        if !chunks.remainder().is_empty() {
+            return Err(invalid("invalid pieces length").into());
+        }
+        let pieces = chunks
+            .map(|chunk| {
+                let mut digest = [0u8; hash::DIGEST_LENGTH_BYTES];
+                digest.copy_from_slice(chunk);
+                Digest::new(digest)
+            })
+            .collect();
+
+        let files = if let Some(entry) = info.get(b"files") {
+            let entries = entry.value.as_list().ok_or_else(|| invalid("invalid files"))?;
+            let mut files = Vec::with_capacity(entries.len());
+            for entry in entries {
+                let length = entry
+                    .get(b"length")
+                    .and_then(|entry| entry.value.as_integer())
+                    .and_then(|length| u64::try_from(length).ok())
+                    .ok_or_else(|| invalid("missing or invalid file length"))?;
+                let path = entry
+                    .get(b"path")
+                    .and_then(|entry| entry.value.as_list())
+                    .filter(|path| !path.is_empty())
+                    .ok_or_else(|| invalid("missing or invalid file path"))?
+                    .iter()
+                    .map(|component| {
+                        let component = component.as_bytes().ok_or_else(|| invalid("invalid file path"))?;
+                        utf8_component(component)
+                    })
+                    .collect::<io::Result<_>>()?;
+                files.push(FileInfo { path, length });
+            }
+            Files::Multiple(files)
+        } else {
+            let length = info
+                .get(b"length")
+                .and_then(|entry| entry.value.as_integer())
+                .and_then(|length| u64::try_from(length).ok())
+                .ok_or_else(|| invalid("missing or invalid length"))?;
+            Files::Single { length }
+        };
+
+        // the lengths come from untrusted data, so their sum is checked
+        let length = match &files {
+            Files::Single { length } => Some(*length),
+            Files::Multiple(files) => {
+                files
+                    .iter()
+                    .try_fold(0u64, |total, file| total.checked_add(file.length))
+            },
+        }
+        .ok_or_else(|| invalid("total length overflows"))?;
+
+        let info = Self {
+            name,
+            piece_length,
+            files,
+            pieces,
+        };
+        if info.pieces.len() as u64 != length.div_ceil(piece_length as u64) {
+            return Err(invalid("number of pieces doesn't match total length").into());
+        }
+        Ok(info)
+    }
+
+    /// Returns the suggested name of the file or directory.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the number of bytes in each piece.
+    #[must_use]
+    pub const fn piece_length(&self) -> usize {
+        self.piece_length
+    }
+
+    /// Returns the described files.
+    #[must_use]
+    pub const fn files(&self) -> &Files {
+        &self.files
+    }
+
+    /// Returns the digests of the pieces.
+    #[must_use]
+    pub fn pieces(&self) -> &[Digest] {
+        &self.pieces
+    }
+
+    /// Returns the total length of all files in bytes.
+    #[must_use]
+    pub fn length(&self) -> u64 {
+        match &self.files {
+            Files::Single { length } => *length,
+            Files::Multiple(files) => files.iter().map(FileInfo::length).sum(),
+        }
+    }
+
+    /// Returns the `pieces` string, which is the concatenation of all piece digests.
+    #[must_use]
+    pub fn pieces_bytes(&self) -> Vec<u8> {
+        self.pieces.iter().flat_map(Digest::as_bytes).copied().collect()
+    }
+
+    /// Returns the bencoded `info` dictionary.
+    #[must_use]
+    pub fn to_bencode(&self) -> Vec<u8> {
+        let mut output = Vec::new();
+        // keys must be sorted as raw byte strings
+        output.push(b'd');
+        match &self.files {
+            Files::Single { length } => {
+                bencode::encode_bytes(&mut output, b"length");
+                bencode::encode_integer(&mut output, *length);
+            },
+            Files::Multiple(files) => {
+                bencode::encode_bytes(&mut output, b"files");
+                output.push(b'l');
+                for file in files {
+                    output.push(b'd');
+                    bencode::encode_bytes(&mut output, b"length");
+                    bencode::encode_integer(&mut output, file.length);
+                    bencode::encode_bytes(&mut output, b"path");
+                    output.push(b'l');
+                    for component in &file.path {
+                        bencode::encode_bytes(&mut output, component.as_bytes());
+                    }
+                    output.push(b'e');
+                    output.push(b'e');
+                }
+                output.push(b'e');
+            },
+        }
+        bencode::encode_bytes(&mut output, b"name");
+        bencode::encode_bytes(&mut output, self.name.as_bytes());
+        bencode::encode_bytes(&mut output, b"piece length");
+        bencode::encode_integer(&mut output, self.piece_length as u64);
+        bencode::encode_bytes(&mut output, b"pieces");
+        bencode::encode_bytes(&mut output, &self.pieces_bytes());
+        output.push(b'e');
+        output
+    }
+
+    /// Returns the info-hash, which is the digest of the bencoded `info` dictionary.
+    #[must_use]
+    pub fn info_hash(&self) -> Digest {
+        SHA1::hash(self.to_bencode())
+    }
+
+    /// Verifies the data located at `path` against this metadata.
+    ///
+    /// Check [`verify`] function for details.
+    pub fn verify(&self, path: impl AsRef<Path>) -> Result<Verification> {
+        let path = path.as_ref();
+        let files: Vec<(PathBuf, u64)> = match &self.files {
+            Files::Single { length } => vec![(path.to_path_buf(), *length)],
+            Files::Multiple(files) => {
+                files
+                    .iter()
+                    .map(|file| (file.path.iter().collect::<PathBuf>(), file.length))
+                    .map(|(relative, length)| (path.join(relative), length))
+                    .collect()
+            },
+        };
+
+        let piece_length = self.piece_length as u64;
+        let mut valid = vec![true; self.pieces.len()];
+        let mut chunker = Chunker::new(self.piece_length);
+        // the index of the piece the chunker processes
+        let mut index = 0;
+        let mut check = |index: &mut usize, digest: Digest| {
+            if let Some(valid) = valid.get_mut(*index) {
+                *valid &= self.pieces[*index] == digest;
+            }
+            *index += 1;
+        };
+        let mut offset = 0u64;
+        // the offset at which hashing resumes after missing data
+        let mut resume = 0u64;
+        let mut missing_ranges = Vec::new();
+        for (path, length) in files {
+            let skip = resume.saturating_sub(offset).min(length);
+            let read = match File::open(&path) {
+                Ok(mut file) => {
+                    file.seek(SeekFrom::Start(skip))?;
+                    skip + read_file(file, length - skip, &mut chunker, |digest| check(&mut index, digest))?
+                },
+                Err(error) if error.kind() == ErrorKind::NotFound => skip,
+                Err(error) => return Err(error.into()),
+            };
+            if read < length {
+                // the pieces overlapping missing data are invalid, so the data is skipped up to the next piece
+                missing_ranges.push((offset + read) / piece_length..=(offset + length - 1) / piece_length);
+                let _ = chunker.finish();
+                resume = (offset + length).div_ceil(piece_length).saturating_mul(piece_length);
+                index = usize::try_from(resume / piece_length).unwrap_or(usize::MAX);
+            }
+            offset += length;
+        }
+        if let Some(digest) = chunker.finish() {
+            check(&mut index, digest);
+        }
+        for range in missing_ranges {
+            for index in range {
+                if let Some(valid) = usize::try_from(index).ok().and_then(|index| valid.get_mut(index)) {
+                    *valid = false;
+                }
+            }
+        }
+        Ok(Verification { pieces: valid })
+    }
+}
+
+/// The result of the verification of on-disk data against torrent metadata.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Verification {
+    pieces: Vec<bool>,
+}
+
+impl Verification {
+    /// Returns the validity of every piece.
+    #[must_use]
+    pub fn pieces(&self) -> &[bool] {
+        &self.pieces
+    }
+
+    /// Returns `true` if all pieces are valid.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.pieces.iter().all(|&valid| valid)
+    }
+
+    /// Returns an iterator over the indices of invalid pieces.
+    pub fn invalid_pieces(&self) -> impl Iterator<Item = usize> + '_ {
+        self.pieces
+            .iter()
+            .enumerate()
+            .filter_map(|(index, &valid)| (!valid).then_some(index))
+    }
+}
+
+/// Collects files of the directory recursively, in the same order in which [`chksum`](crate::chksum) processes them.
+fn walk(directory: &Path, prefix: &[String], files: &mut Vec<(PathBuf, Vec<String>)>) -> io::Result<()> {
+    let mut paths = read_dir(directory)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    paths.sort();
+    for path in paths {
+        let mut components = prefix.to_vec();
+        components.push(utf8_name(&path)?);
+        if path.metadata()?.is_dir() {
+            walk(&path, &components, files)?;
+        } else {
+            files.push((path, components));
+        }
+    }
+    Ok(())
+}
+
+/// Hashes the whole file and returns its length.
+fn hash_file(path: &Path, chunker: &mut Chunker, pieces: &mut Vec<Digest>) -> io::Result<u64> {
+    let file = File::open(path)?;
+    read_file(file, u64::MAX, chunker, |digest| pieces.push(digest))
+}
+
+/// Hashes at most `limit` bytes of the file and returns the number of processed bytes.
+fn read_file(file: File, limit: u64, chunker: &mut Chunker, mut callback: impl FnMut(Digest)) -> io::Result<u64> {
+    let mut reader = file.take(limit);
+    let mut buffer = vec![0u8; BUFFER_LENGTH];
+    let mut length = 0;
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => {
+                chunker.update(&buffer[..n], &mut callback);
+                length += n as u64;
+            },
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(length)
+}
+
+fn utf8_name(path: &Path) -> io::Result<String> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(String::from)
+        .ok_or_else(|| invalid("file name is not valid UTF-8"))
+}
+
+/// Validates a single path component, which must not allow to escape the torrent directory.
+fn utf8_component(component: &[u8]) -> io::Result<String> {
+    let component = std::str::from_utf8(component).map_err(|_| invalid("path is not valid UTF-8"))?;
+    if component.is_empty() || component == "." || component == ".." || component.contains(['/', '\\']) {
+        return Err(invalid("invalid path component"));
+    }
+    Ok(component.to_owned())
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
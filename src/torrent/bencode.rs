@@ -0,0 +1,173 @@
+//! A minimal implementation of the bencoding used by the `.torrent` files.
+
+use std::io::{self, ErrorKind};
+
+/// The maximum nesting level of lists and dictionaries accepted by the decoder.
+const MAX_DEPTH: usize = 64;
+
+/// A decoded bencode value borrowing from the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Value<'a> {
+    Integer(i64),
+    Bytes(&'a [u8]),
+    List(Vec<Value<'a>>),
+    Dictionary(Vec<Entry<'a>>),
+}
+
+/// An entry of a decoded dictionary.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Entry<'a> {
+    pub(crate) key: &'a [u8],
+    pub(crate) value: Value<'a>,
+    /// The encoded form of the value, exactly as it appears in the input.
+    pub(crate) raw: &'a [u8],
+}
+
+impl<'a> Value<'a> {
+    /// Returns the entry with the given key if this value is a dictionary.
+    pub(crate) fn get(&self, key: &[u8]) -> Option<&Entry<'a>> {
+        match self {
+            Self::Dictionary(entries) => entries.iter().find(|entry| entry.key == key),
+            _ => None,
+        }
+    }
+
+    pub(crate) const fn as_integer(&self) -> Option<i64> {
+        match self {
+            Self::Integer(integer) => Some(*integer),
+            _ => None,
+        }
+    }
+
+    pub(crate) const fn as_bytes(&self) -> Option<&'a [u8]> {
+        match self {
+            Self::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_list(&self) -> Option<&[Value<'a>]> {
+        match self {
+            Self::List(list) => Some(list),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes a single value which must span the whole input.
+pub(crate) fn decode(input: &[u8]) -> io::Result<Value<'_>> {
+    let mut decoder = Decoder { input, position: 0 };
+    let value = decoder.value(0)?;
+    if decoder.position != input.len() {
+        return Err(invalid("trailing data after bencoded value"));
+    }
+    Ok(value)
+}
+
+struct Decoder<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn peek(&self) -> io::Result<u8> {
+        self.input
+            .get(self.position)
+            .copied()
+            .ok_or_else(|| invalid("unexpected end of bencoded data"))
+    }
+
+    fn until(&mut self, delimiter: u8) -> io::Result<&'a [u8]> {
+        let rest = &self.input[self.position..];
+        let length = rest
+            .iter()
+            .position(|&byte| byte == delimiter)
+            .ok_or_else(|| invalid("unexpected end of bencoded data"))?;
+        self.position += length + 1;
+        Ok(&rest[..length])
+    }
+
+    fn value(&mut self, depth: usize) -> io::Result<Value<'a>> {
+        if depth > MAX_DEPTH {
+            return Err(invalid("bencoded data is nested too deeply"));
+        }
+        match self.peek()? {
+            b'i' => {
+                self.position += 1;
+                let digits = self.until(b'e')?;
+                parse_integer(digits).map(Value::Integer)
+            },
+            b'l' => {
+                self.position += 1;
+                let mut list = Vec::new();
+                while self.peek()? != b'e' {
+                    list.push(self.value(depth + 1)?);
+                }
+                self.position += 1;
+                Ok(Value::List(list))
+            },
+            b'd' => {
+                self.position += 1;
+                let mut entries = Vec::new();
+                while self.peek()? != b'e' {
+                    let key = self.bytes()?;
+                    let start = self.position;
+                    let value = self.value(depth + 1)?;
+                    let raw = &self.input[start..self.position];
+                    entries.push(Entry { key, value, raw });
+                }
+                self.position += 1;
+                Ok(Value::Dictionary(entries))
+            },
+            b'0'..=b'9' => self.bytes().map(Value::Bytes),
+            _ => Err(invalid("invalid bencoded value")),
+        }
+    }
+
+    fn bytes(&mut self) -> io::Result<&'a [u8]> {
+        let digits = self.until(b':')?;
+        let length = parse_integer(digits)?;
+        let length = usize::try_from(length).map_err(|_| invalid("invalid bencoded string length"))?;
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|&end| end <= self.input.len())
+            .ok_or_else(|| invalid("unexpected end of bencoded data"))?;
+        let bytes = &self.input[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+}
+
+fn parse_integer(digits: &[u8]) -> io::Result<i64> {
+    let valid = match digits {
+        [b'0'] => true,
+        [b'-', b'1'..=b'9', rest @ ..] | [b'1'..=b'9', rest @ ..] => rest.iter().all(u8::is_ascii_digit),
+        _ => false,
+    };
+    if !valid {
+        return Err(invalid("invalid bencoded integer"));
+    }
+    std::str::from_utf8(digits)
+        .ok()
+        .and_then(|digits| digits.parse().ok())
+        .ok_or_else(|| invalid("invalid bencoded integer"))
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+/// Appends the encoded integer to the output.
+pub(crate) fn encode_integer(output: &mut Vec<u8>, integer: u64) {
+    output.push(b'i');
+    output.extend(integer.to_string().as_bytes());
+    output.push(b'e');
+}
+
+/// Appends the encoded byte string to the output.
+pub(crate) fn encode_bytes(output: &mut Vec<u8>, bytes: &[u8]) {
+    output.extend(bytes.len().to_string().as_bytes());
+    output.push(b':');
+    output.extend(bytes);
+}
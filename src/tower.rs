@@ -0,0 +1,311 @@
+//! This module provides a [`tower`](https://docs.rs/tower) middleware which computes digests of HTTP request and response bodies.
+//!
+//! The [`HashLayer`] wraps the bodies in a [`HashBody`], which hashes the data while it's streamed, without buffering it.
+//! A [`BodyDigest`] handle is inserted into the extensions of the request and of the response, and holds the digest once the body has been read to the end.
+//!
+//! # Verification
+//!
+//! Created with [`HashLayer::verify`], the layer compares the digest of every request body with the hexadecimal digest sent by the client in the given header.
+//! As the digest is known only at the end of the body, a mismatch is reported as an [`Error::Mismatch`] error of the last poll of the body, so the service which collects the body rejects the request.
+//! A malformed header value fails the body on the first poll with [`Error::InvalidHeader`], while requests without the header aren't verified.
+//!
+//! # Enabling
+//!
+//! This module is optional and can be enabled using the `tower` Cargo feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::convert::Infallible;
+//!
+//! use bytes::Bytes;
+//! use chksum_sha1::tower::{BodyDigest, HashBody, HashLayer};
+//! use http::{HeaderName, Request, Response, StatusCode};
+//! use http_body_util::{BodyExt, Full};
+//! use tower::{service_fn, Layer};
+//!
+//! async fn upload(
+//!     request: Request<HashBody<Full<Bytes>>>,
+//! ) -> Result<Response<Full<Bytes>>, Infallible> {
+//!     let digest = request.extensions().get::<BodyDigest>().cloned().unwrap();
+//!     match request.into_body().collect().await {
+//!         Ok(_) => {
+//!             let digest = digest.get().unwrap();
+//!             Ok(Response::new(Full::from(digest.to_hex_lowercase())))
+//!         },
+//!         Err(error) => {
+//!             let mut response = Response::new(Full::from(error.to_string()));
+//!             *response.status_mut() = StatusCode::BAD_REQUEST;
+//!             Ok(response)
+//!         },
+//!     }
+//! }
+//!
+//! let layer = HashLayer::verify(HeaderName::from_static("x-content-sha1"));
+//! let service = layer.layer(service_fn(upload));
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+use std::task::{ready, Context, Poll};
+
+use bytes::{Buf, Bytes};
+use http::{HeaderName, HeaderValue, Request, Response};
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{Digest, SHA1};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A handle to the digest of a body, available once the body has been read to the end.
+///
+/// Clones of the handle share the same digest.
+#[derive(Clone, Debug, Default)]
+pub struct BodyDigest {
+    digest: Arc<OnceLock<Digest>>,
+}
+
+impl BodyDigest {
+    /// Returns the digest of the body, or `None` if the body hasn't been read to the end yet.
+    #[must_use]
+    pub fn get(&self) -> Option<Digest> {
+        self.digest.get().copied()
+    }
+}
+
+/// An error of a [`HashBody`].
+#[derive(Debug)]
+pub enum Error {
+    /// The wrapped body has failed.
+    Body(BoxError),
+    /// The header with the expected digest isn't a valid hexadecimal digest.
+    InvalidHeader,
+    /// The digest of the body doesn't match the expected digest.
+    Mismatch {
+        /// The digest sent by the client.
+        expected: Digest,
+        /// The digest of the body.
+        computed: Digest,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Body(error) => Display::fmt(error, f),
+            Self::InvalidHeader => write!(f, "Invalid digest header"),
+            Self::Mismatch { expected, computed } => {
+                write!(f, "Body digest {computed} doesn't match expected digest {expected}")
+            },
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Body(error) => Some(error.as_ref()),
+            Self::InvalidHeader | Self::Mismatch { .. } => None,
+        }
+    }
+}
+
+/// The digest the body is verified against.
+#[derive(Clone, Copy, Debug)]
+enum Expected {
+    None,
+    Digest(Digest),
+    Invalid,
+}
+
+pin_project! {
+    /// A body which computes the digest of the data of the wrapped body.
+    #[derive(Debug)]
+    pub struct HashBody<B> {
+        #[pin]
+        inner: B,
+        hash: SHA1,
+        digest: BodyDigest,
+        expected: Expected,
+        finished: bool,
+    }
+}
+
+impl<B> HashBody<B> {
+    /// Wraps the body.
+    #[must_use]
+    pub fn new(inner: B) -> Self {
+        Self::with_expected(inner, Expected::None)
+    }
+
+    /// Wraps the body, which fails at the end if its digest doesn't match the expected one.
+    #[must_use]
+    pub fn verify(inner: B, expected: Digest) -> Self {
+        Self::with_expected(inner, Expected::Digest(expected))
+    }
+
+    fn with_expected(inner: B, expected: Expected) -> Self {
+        Self {
+            inner,
+            hash: SHA1::new(),
+            digest: BodyDigest::default(),
+            expected,
+            finished: false,
+        }
+    }
+
+    /// Returns the handle to the digest of the body.
+    #[must_use]
+    pub fn digest(&self) -> BodyDigest {
+        self.digest.clone()
+    }
+}
+
+impl<B> Body for HashBody<B>
+where
+    B: Body,
+    B::Error: Into<BoxError>,
+{
+    type Data = Bytes;
+    type Error = Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        if *this.finished {
+            return Poll::Ready(None);
+        }
+        if let Expected::Invalid = this.expected {
+            *this.finished = true;
+            return Poll::Ready(Some(Err(Error::InvalidHeader)));
+        }
+
+        match ready!(this.inner.poll_frame(cx)) {
+            Some(Ok(frame)) => {
+                let frame = frame.map_data(|mut data| {
+                    let data = data.copy_to_bytes(data.remaining());
+                    this.hash.update(&data);
+                    data
+                });
+                Poll::Ready(Some(Ok(frame)))
+            },
+            Some(Err(error)) => Poll::Ready(Some(Err(Error::Body(error.into())))),
+            None => {
+                *this.finished = true;
+                let computed = this.hash.digest();
+                // the digest is set only here, and only once
+                let _ = this.digest.digest.set(computed);
+                match *this.expected {
+                    Expected::Digest(expected) if expected != computed => {
+                        Poll::Ready(Some(Err(Error::Mismatch { expected, computed })))
+                    },
+                    _ => Poll::Ready(None),
+                }
+            },
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        // the end of the wrapped body must be polled to finish the digest
+        self.finished
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// A layer which wraps the request and response bodies in a [`HashBody`].
+#[derive(Clone, Debug, Default)]
+pub struct HashLayer {
+    header: Option<HeaderName>,
+}
+
+impl HashLayer {
+    /// Creates a layer which computes the digests of the bodies.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a layer which also verifies the request bodies against the hexadecimal digest sent in the header.
+    #[must_use]
+    pub fn verify(header: HeaderName) -> Self {
+        let header = Some(header);
+        Self { header }
+    }
+}
+
+impl<S> Layer<S> for HashLayer {
+    type Service = HashService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        let header = self.header.clone();
+        HashService { inner, header }
+    }
+}
+
+/// A service which wraps the request and response bodies in a [`HashBody`].
+#[derive(Clone, Debug)]
+pub struct HashService<S> {
+    inner: S,
+    header: Option<HeaderName>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for HashService<S>
+where
+    S: Service<Request<HashBody<ReqBody>>, Response = Response<ResBody>>,
+{
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future>;
+    type Response = Response<HashBody<ResBody>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let expected = match self.header.as_ref().and_then(|header| request.headers().get(header)) {
+            Some(value) => parse(value).map_or(Expected::Invalid, Expected::Digest),
+            None => Expected::None,
+        };
+        let (mut parts, body) = request.into_parts();
+        let body = HashBody::with_expected(body, expected);
+        parts.extensions.insert(body.digest());
+        let inner = self.inner.call(Request::from_parts(parts, body));
+        ResponseFuture { inner }
+    }
+}
+
+pin_project! {
+    /// The response future of [`HashService`].
+    #[derive(Debug)]
+    pub struct ResponseFuture<F> {
+        #[pin]
+        inner: F,
+    }
+}
+
+impl<F, ResBody, E> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<Response<ResBody>, E>>,
+{
+    type Output = Result<Response<HashBody<ResBody>>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let response = ready!(self.project().inner.poll(cx))?;
+        let (mut parts, body) = response.into_parts();
+        let body = HashBody::new(body);
+        parts.extensions.insert(body.digest());
+        Poll::Ready(Ok(Response::from_parts(parts, body)))
+    }
+}
+
+/// Parses the hexadecimal digest of the header value.
+fn parse(value: &HeaderValue) -> Option<Digest> {
+    Digest::decode_hex(value.as_bytes())
+}
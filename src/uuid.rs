@@ -0,0 +1,140 @@
+//! This module provides generation of name-based UUIDs of version 5, as defined in [RFC 9562](https://www.rfc-editor.org/rfc/rfc9562#section-5.5).
+//!
+//! A UUID of version 5 is derived from the SHA-1 digest of a namespace UUID followed by a name, so the same name within the same namespace always yields the same UUID.
+//!
+//! # Example
+//!
+//! ```rust
+//! use chksum_sha1 as sha1;
+//! use chksum_sha1::uuid::Uuid;
+//!
+//! let uuid = sha1::uuid_v5(Uuid::NAMESPACE_DNS, "python.org");
+//! assert_eq!(uuid.version(), 5);
+//! # #[cfg(feature = "alloc")]
+//! assert_eq!(uuid.to_string(), "886313e1-3b8a-5372-9b90-0c9aee199e5d");
+//! ```
+
+use core::fmt::{self, Display, Formatter};
+
+use crate::SHA1;
+
+/// The length of the UUID in bytes.
+pub const UUID_LENGTH_BYTES: usize = 16;
+
+/// The length of the canonical string form of the UUID.
+const UUID_LENGTH_CHARS: usize = 36;
+
+/// A universally unique identifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uuid([u8; UUID_LENGTH_BYTES]);
+
+impl Uuid {
+    /// The namespace for fully qualified domain names.
+    pub const NAMESPACE_DNS: Self = Self([
+        0x6B, 0xA7, 0xB8, 0x10, 0x9D, 0xAD, 0x11, 0xD1, 0x80, 0xB4, 0x00, 0xC0, 0x4F, 0xD4, 0x30, 0xC8,
+    ]);
+    /// The namespace for ISO object identifiers.
+    pub const NAMESPACE_OID: Self = Self([
+        0x6B, 0xA7, 0xB8, 0x12, 0x9D, 0xAD, 0x11, 0xD1, 0x80, 0xB4, 0x00, 0xC0, 0x4F, 0xD4, 0x30, 0xC8,
+    ]);
+    /// The namespace for URLs.
+    pub const NAMESPACE_URL: Self = Self([
+        0x6B, 0xA7, 0xB8, 0x11, 0x9D, 0xAD, 0x11, 0xD1, 0x80, 0xB4, 0x00, 0xC0, 0x4F, 0xD4, 0x30, 0xC8,
+    ]);
+    /// The namespace for X.500 distinguished names.
+    pub const NAMESPACE_X500: Self = Self([
+        0x6B, 0xA7, 0xB8, 0x14, 0x9D, 0xAD, 0x11, 0xD1, 0x80, 0xB4, 0x00, 0xC0, 0x4F, 0xD4, 0x30, 0xC8,
+    ]);
+
+    /// Creates a new UUID from its bytes in the network byte order.
+    #[must_use]
+    pub const fn new(uuid: [u8; UUID_LENGTH_BYTES]) -> Self {
+        Self(uuid)
+    }
+
+    /// Returns a byte slice of the UUID's contents.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8] {
+        let Self(inner) = self;
+        inner
+    }
+
+    /// Consumes the UUID, returning the UUID bytes.
+    #[must_use]
+    pub const fn into_inner(self) -> [u8; UUID_LENGTH_BYTES] {
+        let Self(inner) = self;
+        inner
+    }
+
+    /// Returns the version stored in the UUID.
+    #[must_use]
+    pub const fn version(&self) -> u8 {
+        self.0[6] >> 4
+    }
+}
+
+/// Formats the UUID in the canonical form of lowercase hexadecimal digits grouped by hyphens, e.g. `886313e1-3b8a-5372-9b90-0c9aee199e5d`.
+impl Display for Uuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        const ALPHABET: &[u8; 16] = b"0123456789abcdef";
+
+        let mut text = [0u8; UUID_LENGTH_CHARS];
+        let mut position = 0;
+        for (index, byte) in self.0.iter().enumerate() {
+            if matches!(index, 4 | 6 | 8 | 10) {
+                text[position] = b'-';
+                position += 1;
+            }
+            text[position] = ALPHABET[usize::from(byte >> 4)];
+            text[position + 1] = ALPHABET[usize::from(byte & 0x0F)];
+            position += 2;
+        }
+        let text = core::str::from_utf8(&text).expect("hexadecimal digits and hyphens must be valid UTF-8");
+        f.pad(text)
+    }
+}
+
+impl From<[u8; UUID_LENGTH_BYTES]> for Uuid {
+    fn from(uuid: [u8; UUID_LENGTH_BYTES]) -> Self {
+        Self::new(uuid)
+    }
+}
+
+impl AsRef<[u8]> for Uuid {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// Generates the name-based UUID of version 5 for the name within the namespace.
+///
+/// # Example
+///
+/// ```rust
+/// use chksum_sha1 as sha1;
+/// use chksum_sha1::uuid::Uuid;
+///
+/// let first = sha1::uuid_v5(Uuid::NAMESPACE_URL, "https://example.com/");
+/// let second = sha1::uuid_v5(Uuid::NAMESPACE_URL, "https://example.com/");
+/// assert_eq!(first, second);
+///
+/// // a custom namespace for the application
+/// let namespace = sha1::uuid_v5(Uuid::NAMESPACE_DNS, "example.com");
+/// let uuid = sha1::uuid_v5(namespace, "user:42");
+/// assert_eq!(uuid.version(), 5);
+/// ```
+#[must_use]
+pub fn uuid_v5(namespace: Uuid, name: impl AsRef<[u8]>) -> Uuid {
+    let mut hash = SHA1::new();
+    hash.update(namespace);
+    hash.update(name);
+    let digest = hash.digest();
+
+    let mut uuid = [0u8; UUID_LENGTH_BYTES];
+    uuid.copy_from_slice(&digest.as_bytes()[..UUID_LENGTH_BYTES]);
+    // the version occupies the high nibble of the 7th byte
+    uuid[6] = (uuid[6] & 0x0F) | 0x50;
+    // the RFC variant occupies the two high bits of the 9th byte
+    uuid[8] = (uuid[8] & 0x3F) | 0x80;
+    Uuid(uuid)
+}
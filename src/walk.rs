@@ -0,0 +1,330 @@
+//! Traversal of files and directories matching the [`Chksumable`](chksum_core::Chksumable) implementations for paths.
+//!
+//! A directory is processed as the concatenation of its entries sorted by path, recursively, and everything else is processed as a file.
+//! Contrary to [`chksum_core`], the data is read in chunks reported to the caller, which allows to observe and interrupt the computation, and every error carries the path and the operation which failed.
+
+use std::fmt::{self, Display, Formatter};
+use std::fs::{self, File, Metadata};
+#[cfg(feature = "async-runtime-tokio")]
+use std::future::Future;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::result;
+
+use crate::{Error, SHA1};
+
+/// The size of the buffer used to read files.
+const BUFFER_LENGTH: usize = 64 * 1024;
+
+/// The file system operation which has failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    /// Reading the metadata of a path.
+    Metadata,
+    /// Listing the entries of a directory.
+    ReadDir,
+    /// Opening a file.
+    Open,
+    /// Reading the data of a file.
+    Read,
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Metadata => write!(f, "read metadata of"),
+            Self::ReadDir => write!(f, "read directory"),
+            Self::Open => write!(f, "open"),
+            Self::Read => write!(f, "read"),
+        }
+    }
+}
+
+/// An error of a file system operation on the given path.
+#[derive(Debug)]
+pub struct PathError {
+    operation: Operation,
+    path: PathBuf,
+    error: Error,
+}
+
+impl PathError {
+    pub(crate) fn new(operation: Operation, path: impl Into<PathBuf>, error: impl Into<Error>) -> Self {
+        let path = path.into();
+        let error = error.into();
+        Self { operation, path, error }
+    }
+
+    /// Returns the operation which has failed.
+    #[must_use]
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    /// Returns the path the operation has failed on.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the underlying error.
+    #[must_use]
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Returns the underlying error, discarding the context.
+    #[must_use]
+    pub fn into_error(self) -> Error {
+        self.error
+    }
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Self { operation, path, error } = self;
+        write!(f, "Cannot {operation} `{}`: {error}", path.display())
+    }
+}
+
+impl std::error::Error for PathError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Converts the error into [`Error::Io`] with the context available through [`io::Error::get_ref`].
+///
+/// The context cannot be attached to [`Error::IsTerminal`], so it's discarded.
+impl From<PathError> for Error {
+    fn from(error: PathError) -> Self {
+        match error.error {
+            Error::Io(ref inner) => Self::Io(io::Error::new(inner.kind(), error)),
+            error => error,
+        }
+    }
+}
+
+/// Returns the files of the path with their metadata, in the order of processing.
+///
+/// Every error is passed to `handle`, which either stops the traversal with its own error or skips the failed path.
+pub(crate) fn files<E>(
+    path: &Path,
+    handle: impl FnMut(PathError) -> result::Result<(), E>,
+) -> result::Result<Vec<(PathBuf, Metadata)>, E> {
+    files_until(path, handle, || Ok(()))
+}
+
+/// Returns the files of the path with their metadata, in the order of processing.
+///
+/// Works like [`files`], but also calls `check` before visiting every path, which stops the traversal if it returns an error.
+pub(crate) fn files_until<E>(
+    path: &Path,
+    mut handle: impl FnMut(PathError) -> result::Result<(), E>,
+    mut check: impl FnMut() -> result::Result<(), E>,
+) -> result::Result<Vec<(PathBuf, Metadata)>, E> {
+    let mut files = Vec::new();
+    let mut paths = vec![path.to_path_buf()];
+    while let Some(path) = paths.pop() {
+        check()?;
+        let metadata = match fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(error) => {
+                handle(PathError::new(Operation::Metadata, path, error))?;
+                continue;
+            },
+        };
+        if metadata.is_dir() {
+            let entries: io::Result<Vec<PathBuf>> =
+                fs::read_dir(&path).and_then(|entries| entries.map(|entry| Ok(entry?.path())).collect());
+            let mut entries = match entries {
+                Ok(entries) => entries,
+                Err(error) => {
+                    handle(PathError::new(Operation::ReadDir, path, error))?;
+                    continue;
+                },
+            };
+            // the entries are popped from the end
+            entries.sort_unstable_by(|x, y| y.cmp(x));
+            paths.extend(entries);
+        } else {
+            files.push((path, metadata));
+        }
+    }
+    Ok(files)
+}
+
+/// Updates the hash with the data of the file, calling `callback` with the length of every chunk.
+///
+/// An error returned by `callback` stops the computation.
+pub(crate) fn update<E>(
+    path: &Path,
+    hash: &mut SHA1,
+    mut callback: impl FnMut(usize) -> result::Result<(), E>,
+) -> result::Result<(), E>
+where
+    E: From<PathError>,
+{
+    read(path, |data| {
+        hash.update(data);
+        callback(data.len())
+    })
+}
+
+/// Reads the data of the file, passing every chunk to `consume`.
+///
+/// An error returned by `consume` stops the reading.
+pub(crate) fn read<E>(path: &Path, mut consume: impl FnMut(&[u8]) -> result::Result<(), E>) -> result::Result<(), E>
+where
+    E: From<PathError>,
+{
+    let mut file = File::open(path).map_err(|error| PathError::new(Operation::Open, path, error))?;
+    if file.is_terminal() {
+        return Err(PathError::new(Operation::Read, path, Error::IsTerminal).into());
+    }
+
+    let mut buffer = vec![0u8; BUFFER_LENGTH];
+    loop {
+        let length = match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(length) => length,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(PathError::new(Operation::Read, path, error).into()),
+        };
+        consume(&buffer[..length])?;
+    }
+    Ok(())
+}
+
+/// Returns the files of the path with their metadata, in the order of processing.
+///
+/// Every error is passed to `handle`, which either stops the traversal with its own error or skips the failed path.
+#[cfg(feature = "async-runtime-tokio")]
+pub(crate) async fn async_files<E>(
+    path: &Path,
+    handle: impl FnMut(PathError) -> result::Result<(), E>,
+) -> result::Result<Vec<(PathBuf, Metadata)>, E> {
+    async_files_until(path, handle, || Ok(())).await
+}
+
+/// Returns the files of the path with their metadata, in the order of processing.
+///
+/// Works like [`async_files`], but also calls `check` before visiting every path, which stops the traversal if it returns an error.
+#[cfg(feature = "async-runtime-tokio")]
+pub(crate) async fn async_files_until<E>(
+    path: &Path,
+    mut handle: impl FnMut(PathError) -> result::Result<(), E>,
+    mut check: impl FnMut() -> result::Result<(), E>,
+) -> result::Result<Vec<(PathBuf, Metadata)>, E> {
+    async fn entries(path: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut entries = Vec::new();
+        let mut dir = tokio::fs::read_dir(path).await?;
+        while let Some(entry) = dir.next_entry().await? {
+            entries.push(entry.path());
+        }
+        Ok(entries)
+    }
+
+    let mut files = Vec::new();
+    let mut paths = vec![path.to_path_buf()];
+    while let Some(path) = paths.pop() {
+        check()?;
+        let metadata = match tokio::fs::metadata(&path).await {
+            Ok(metadata) => metadata,
+            Err(error) => {
+                handle(PathError::new(Operation::Metadata, path, error))?;
+                continue;
+            },
+        };
+        if metadata.is_dir() {
+            let mut entries = match entries(&path).await {
+                Ok(entries) => entries,
+                Err(error) => {
+                    handle(PathError::new(Operation::ReadDir, path, error))?;
+                    continue;
+                },
+            };
+            // the entries are popped from the end
+            entries.sort_unstable_by(|x, y| y.cmp(x));
+            paths.extend(entries);
+        } else {
+            files.push((path, metadata));
+        }
+    }
+    Ok(files)
+}
+
+/// Updates the hash with the data of the file, calling `callback` with the length of every chunk.
+///
+/// An error returned by `callback` stops the computation.
+#[cfg(feature = "async-runtime-tokio")]
+pub(crate) async fn async_update<E>(
+    path: &Path,
+    hash: &mut SHA1,
+    mut callback: impl FnMut(usize) -> result::Result<(), E>,
+) -> result::Result<(), E>
+where
+    E: From<PathError>,
+{
+    async_read(path, |data| {
+        hash.update(data);
+        callback(data.len())
+    })
+    .await
+}
+
+/// Updates the hash with the data of the file, calling `callback` with the length of every chunk.
+///
+/// Works like [`async_update`], but also stops with the error returned by `until` as soon as it completes, even while waiting for the data.
+#[cfg(feature = "async-runtime-tokio")]
+pub(crate) async fn async_update_until<E>(
+    path: &Path,
+    hash: &mut SHA1,
+    callback: impl FnMut(usize) -> result::Result<(), E>,
+    until: impl Future<Output = E>,
+) -> result::Result<(), E>
+where
+    E: From<PathError>,
+{
+    tokio::select! {
+        biased;
+        error = until => Err(error),
+        result = async_update(path, hash, callback) => result,
+    }
+}
+
+/// Reads the data of the file, passing every chunk to `consume`.
+///
+/// An error returned by `consume` stops the reading.
+#[cfg(feature = "async-runtime-tokio")]
+pub(crate) async fn async_read<E>(
+    path: &Path,
+    mut consume: impl FnMut(&[u8]) -> result::Result<(), E>,
+) -> result::Result<(), E>
+where
+    E: From<PathError>,
+{
+    use tokio::io::AsyncReadExt;
+
+    let file = tokio::fs::File::open(path)
+        .await
+        .map_err(|error| PathError::new(Operation::Open, path, error))?;
+    // reading a terminal would wait for the user input, so it's rejected the same way as by `read`
+    let file = file.into_std().await;
+    if file.is_terminal() {
+        return Err(PathError::new(Operation::Read, path, Error::IsTerminal).into());
+    }
+    let mut file = tokio::fs::File::from_std(file);
+    let mut buffer = vec![0u8; BUFFER_LENGTH];
+    loop {
+        let length = file
+            .read(&mut buffer)
+            .await
+            .map_err(|error| PathError::new(Operation::Read, path, error))?;
+        if length == 0 {
+            break;
+        }
+        consume(&buffer[..length])?;
+    }
+    Ok(())
+}
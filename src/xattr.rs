@@ -0,0 +1,230 @@
+//! This module provides storing of digests in extended attributes of files.
+//!
+//! The digest is stored in the [`DIGEST_ATTRIBUTE`] as lowercase hexadecimal digits, together with the modification time of the file at the moment of hashing in the [`MODIFIED_ATTRIBUTE`] as nanoseconds since the Unix epoch.
+//! The modification time allows [`verify`] to detect files modified after their digests have been stored, without reading their data.
+//!
+//! # Enabling
+//!
+//! This module is optional and can be enabled using the `xattr` Cargo feature.
+//! It's available only on Linux, Android and Apple platforms.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! # use chksum_sha1::Result;
+//! use chksum_sha1::xattr::{self, Status};
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! xattr::store(path)?;
+//! // later, e.g. on another run
+//! match xattr::verify(path)? {
+//!     Status::Valid => println!("valid"),
+//!     Status::Mismatch { stored, computed } => {
+//!         println!("corrupted, expected {stored}, got {computed}")
+//!     },
+//!     Status::Stale => println!("modified since the digest was stored"),
+//!     Status::Missing => println!("no stored digest"),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::{File, Metadata};
+use std::io::{self, ErrorKind};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rustix::fs::{fgetxattr, fsetxattr, XattrFlags};
+use rustix::io::Errno;
+
+use crate::{Digest, Result};
+
+/// The name of the extended attribute with the digest.
+pub const DIGEST_ATTRIBUTE: &str = "user.sha1";
+
+/// The name of the extended attribute with the modification time of the file at the moment of hashing.
+pub const MODIFIED_ATTRIBUTE: &str = "user.sha1.mtime";
+
+/// The error returned when the attribute doesn't exist.
+#[cfg(target_vendor = "apple")]
+const MISSING: Errno = Errno::NOATTR;
+#[cfg(not(target_vendor = "apple"))]
+const MISSING: Errno = Errno::NODATA;
+
+/// The digest stored in the extended attributes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Attributes {
+    digest: Digest,
+    modified: Option<SystemTime>,
+}
+
+impl Attributes {
+    /// Returns the stored digest.
+    #[must_use]
+    pub fn digest(&self) -> Digest {
+        self.digest
+    }
+
+    /// Returns the modification time of the file at the moment of hashing, if it has been stored.
+    #[must_use]
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.modified
+    }
+}
+
+/// The result of the verification of a file against its stored digest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The file matches the stored digest.
+    Valid,
+    /// The file hasn't been modified since the digest was stored, but it doesn't match the digest anymore.
+    Mismatch {
+        /// The stored digest.
+        stored: Digest,
+        /// The digest of the current data.
+        computed: Digest,
+    },
+    /// The file has been modified since the digest was stored, so the digest is outdated.
+    Stale,
+    /// There is no stored digest.
+    Missing,
+}
+
+/// Computes the hash of the file and stores it in the extended attributes.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let digest = sha1::xattr::store(path)?;
+/// assert_eq!(
+///     sha1::xattr::read(path)?.map(|attributes| attributes.digest()),
+///     Some(digest)
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn store(path: impl AsRef<Path>) -> Result<Digest> {
+    let file = File::open(path)?;
+    // the time is taken before hashing, so any modification in the meantime makes the digest stale
+    let modified = modified(&file.metadata()?)?;
+    let digest = crate::chksum(&file)?;
+    fsetxattr(
+        &file,
+        DIGEST_ATTRIBUTE,
+        format!("{digest:x}").as_bytes(),
+        XattrFlags::empty(),
+    )
+    .map_err(io::Error::from)?;
+    fsetxattr(
+        &file,
+        MODIFIED_ATTRIBUTE,
+        modified.to_string().as_bytes(),
+        XattrFlags::empty(),
+    )
+    .map_err(io::Error::from)?;
+    Ok(digest)
+}
+
+/// Reads the digest stored in the extended attributes of the file, if any.
+///
+/// # Errors
+///
+/// Returns an error if the attributes cannot be read or their values are invalid.
+pub fn read(path: impl AsRef<Path>) -> Result<Option<Attributes>> {
+    let file = File::open(path)?;
+    let attributes = read_file(&file)?;
+    Ok(attributes.map(|(digest, modified)| {
+        Attributes {
+            digest,
+            modified: modified.map(system_time),
+        }
+    }))
+}
+
+/// Verifies the file against the digest stored in its extended attributes.
+///
+/// The data is read only if the modification time of the file matches the stored one.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// # use chksum_sha1::Result;
+/// use chksum_sha1::xattr::{self, Status};
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// if xattr::verify(path)? != Status::Valid {
+///     xattr::store(path)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify(path: impl AsRef<Path>) -> Result<Status> {
+    let file = File::open(path)?;
+    let Some((stored, stored_modified)) = read_file(&file)? else {
+        return Ok(Status::Missing);
+    };
+    if stored_modified != Some(modified(&file.metadata()?)?) {
+        return Ok(Status::Stale);
+    }
+    let computed = crate::chksum(&file)?;
+    if computed == stored {
+        Ok(Status::Valid)
+    } else {
+        Ok(Status::Mismatch { stored, computed })
+    }
+}
+
+/// Reads the stored digest and modification time of the file.
+fn read_file(file: &File) -> io::Result<Option<(Digest, Option<i128>)>> {
+    fn invalid(name: &str) -> io::Error {
+        io::Error::new(ErrorKind::InvalidData, format!("invalid `{name}` extended attribute"))
+    }
+
+    // both values are much shorter than the buffer, so a longer value cannot be valid either
+    let mut buffer = [0u8; 64];
+    let digest = match fgetxattr(file, DIGEST_ATTRIBUTE, &mut buffer) {
+        Ok(length) => Digest::decode_hex(&buffer[..length]).ok_or_else(|| invalid(DIGEST_ATTRIBUTE))?,
+        Err(MISSING) => return Ok(None),
+        Err(Errno::RANGE) => return Err(invalid(DIGEST_ATTRIBUTE)),
+        Err(error) => return Err(error.into()),
+    };
+    let modified = match fgetxattr(file, MODIFIED_ATTRIBUTE, &mut buffer) {
+        Ok(length) => {
+            let modified = std::str::from_utf8(&buffer[..length])
+                .ok()
+                .and_then(|modified| modified.parse().ok());
+            Some(modified.ok_or_else(|| invalid(MODIFIED_ATTRIBUTE))?)
+        },
+        Err(MISSING) => None,
+        Err(Errno::RANGE) => return Err(invalid(MODIFIED_ATTRIBUTE)),
+        Err(error) => return Err(error.into()),
+    };
+    Ok(Some((digest, modified)))
+}
+
+/// Returns the modification time of the file in nanoseconds since the Unix epoch.
+fn modified(metadata: &Metadata) -> io::Result<i128> {
+    let modified = metadata.modified()?;
+    let nanos = match modified.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_nanos() as i128,
+        Err(error) => -(error.duration().as_nanos() as i128),
+    };
+    Ok(nanos)
+}
+
+/// Converts nanoseconds since the Unix epoch to the system time.
+fn system_time(nanos: i128) -> SystemTime {
+    let duration = Duration::from_nanos(nanos.unsigned_abs().try_into().unwrap_or(u64::MAX));
+    if nanos < 0 {
+        UNIX_EPOCH - duration
+    } else {
+        UNIX_EPOCH + duration
+    }
+}
@@ -0,0 +1,488 @@
+//! This module provides computation of digests of entries of zip archives, e.g. JAR, wheel or APK files, and handling of JAR manifests.
+//!
+//! The digest of an entry is computed over its decompressed data, so it's independent of the compression method and level.
+//!
+//! # JAR Manifests
+//!
+//! The `META-INF/MANIFEST.MF` file of a JAR archive lists the `SHA1-Digest` of the entries, encoded in Base64.
+//! A [`Manifest`] can be generated from the entries of an archive and rendered with [`Display`], or parsed from an existing file and verified against the entries.
+//!
+//! # Enabling
+//!
+//! This module is optional and can be enabled using the `zip` Cargo feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::path::Path;
+//! use std::fs::File;
+//!
+//! # use chksum_sha1::Result;
+//! use chksum_sha1 as sha1;
+//!
+//! # fn wrapper(path: &Path) -> Result<()> {
+//! let entries = sha1::zip::entries(File::open(path)?)?;
+//! for entry in &entries {
+//!     println!("{}  {}", entry.digest(), entry.name());
+//! }
+//!
+//! let verification = sha1::zip::verify(File::open(path)?)?;
+//! assert!(verification.is_valid());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, ErrorKind, Read, Seek};
+
+use crate::{base64, Digest, Result, DIGEST_LENGTH_BYTES, SHA1};
+
+/// The path of the manifest within a JAR archive.
+pub const MANIFEST_NAME: &str = "META-INF/MANIFEST.MF";
+
+/// The maximum length of a manifest line in bytes, excluding the line break.
+const LINE_LENGTH: usize = 72;
+
+/// An entry of the archive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    name: String,
+    is_dir: bool,
+    size: u64,
+    digest: Digest,
+}
+
+impl Entry {
+    /// Returns the name of the entry, with components separated by `/`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns `true` if the entry is a directory.
+    #[must_use]
+    pub fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+
+    /// Returns the size of the decompressed data of the entry.
+    #[must_use]
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the digest of the decompressed data of the entry.
+    #[must_use]
+    pub fn digest(&self) -> Digest {
+        self.digest
+    }
+}
+
+/// Reads the archive and computes the digests of all its entries, in the order of the central directory.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::File;
+///
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let entries = sha1::zip::entries(File::open(path)?)?;
+/// for entry in entries.iter().filter(|entry| !entry.is_dir()) {
+///     println!("{}  {}", entry.digest(), entry.name());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn entries(reader: impl Read + Seek) -> Result<Vec<Entry>> {
+    let (entries, _) = read(reader, false)?;
+    Ok(entries)
+}
+
+/// Verifies the entries of the JAR archive against its `META-INF/MANIFEST.MF` file.
+///
+/// Returns an error if the archive doesn't contain the manifest or the manifest is malformed.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::Path;
+/// use std::fs::File;
+///
+/// # use chksum_sha1::Result;
+/// use chksum_sha1 as sha1;
+///
+/// # fn wrapper(path: &Path) -> Result<()> {
+/// let verification = sha1::zip::verify(File::open(path)?)?;
+/// for mismatch in verification.mismatches() {
+///     eprintln!("{mismatch}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify(reader: impl Read + Seek) -> Result<Verification> {
+    let (entries, manifest) = read(reader, true)?;
+    let manifest = manifest.ok_or_else(|| io::Error::new(ErrorKind::NotFound, "missing manifest"))?;
+    let manifest = Manifest::parse(&manifest)?;
+    Ok(manifest.verify(&entries))
+}
+
+/// The per-entry digests of a JAR manifest.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    entries: Vec<(String, Digest)>,
+}
+
+impl Manifest {
+    /// Creates a manifest listing all files of the archive.
+    ///
+    /// Directories, the manifest itself and the signature files in `META-INF` are not listed, as required by the JAR specification.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::path::Path;
+    /// use std::fs::{write, File};
+    ///
+    /// # use chksum_sha1::Result;
+    /// use chksum_sha1 as sha1;
+    /// use chksum_sha1::zip::Manifest;
+    ///
+    /// # fn wrapper(path: &Path) -> Result<()> {
+    /// let entries = sha1::zip::entries(File::open(path)?)?;
+    /// let manifest = Manifest::new(&entries);
+    /// write("MANIFEST.MF", manifest.to_string())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn new(entries: &[Entry]) -> Self {
+        let entries = entries
+            .iter()
+            .filter(|entry| is_listed(entry))
+            .map(|entry| (entry.name.clone(), entry.digest))
+            .collect();
+        Self { entries }
+    }
+
+    /// Parses the manifest, collecting the `SHA1-Digest` attributes of all sections.
+    ///
+    /// Sections without the `SHA1-Digest` attribute, e.g. listing only digests of other algorithms, are skipped.
+    /// Other attributes are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use chksum_sha1::Result;
+    /// use chksum_sha1 as sha1;
+    /// use chksum_sha1::zip::Manifest;
+    ///
+    /// # fn wrapper() -> Result<()> {
+    /// let manifest = Manifest::parse(
+    ///     b"Manifest-Version: 1.0\r\n\r\nName: a.txt\r\nSHA1-Digest: w0mcJylzCn+AfvuGdqkty2+KP48=\r\n\r\n",
+    /// )?;
+    /// assert_eq!(manifest.get("a.txt"), Some(sha1::hash("example")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse(manifest: &[u8]) -> Result<Self> {
+        let manifest = std::str::from_utf8(manifest).map_err(|_| invalid("manifest is not valid UTF-8"))?;
+        let mut entries = Vec::new();
+        let mut sections = sections(manifest)?.into_iter();
+        // the main section describes the archive itself
+        sections.next();
+        for section in sections {
+            let name = attribute(&section, "Name").ok_or_else(|| invalid("manifest section without name"))?;
+            if let Some(digest) = attribute(&section, "SHA1-Digest") {
+                let digest = base64::decode(digest)
+                    .and_then(|digest| <[u8; DIGEST_LENGTH_BYTES]>::try_from(digest).ok())
+                    .ok_or_else(|| invalid("invalid manifest digest"))?;
+                entries.push((name.to_owned(), Digest::new(digest)));
+            }
+        }
+        Ok(Self { entries })
+    }
+
+    /// Returns the digest listed for the entry.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<Digest> {
+        self.entries
+            .iter()
+            .find(|(entry, _)| entry == name)
+            .map(|&(_, digest)| digest)
+    }
+
+    /// Returns an iterator over the names and digests of the listed entries, in the order of the manifest.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Digest)> {
+        self.entries.iter().map(|(name, digest)| (name.as_str(), *digest))
+    }
+
+    /// Returns the number of listed entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no entry is listed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Verifies the entries against the digests listed in the manifest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::path::Path;
+    /// use std::fs::{read, File};
+    ///
+    /// # use chksum_sha1::Result;
+    /// use chksum_sha1 as sha1;
+    /// use chksum_sha1::zip::Manifest;
+    ///
+    /// # fn wrapper(manifest: &Path, path: &Path) -> Result<()> {
+    /// let manifest = Manifest::parse(&read(manifest)?)?;
+    /// let entries = sha1::zip::entries(File::open(path)?)?;
+    /// assert!(manifest.verify(&entries).is_valid());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn verify(&self, entries: &[Entry]) -> Verification {
+        // the archives may have tens of thousands of entries, so both sides are looked up by name
+        let mut computed = HashMap::with_capacity(entries.len());
+        for entry in entries {
+            computed.entry(entry.name.as_str()).or_insert(entry);
+        }
+        let listed: HashSet<&str> = self.entries.iter().map(|(name, _)| name.as_str()).collect();
+
+        let mut mismatches = Vec::new();
+        for (name, expected) in &self.entries {
+            match computed.get(name.as_str()) {
+                Some(entry) if entry.digest != *expected => {
+                    mismatches.push(Mismatch::Modified {
+                        name: name.clone(),
+                        expected: *expected,
+                        computed: entry.digest,
+                    })
+                },
+                Some(_) => {},
+                None => mismatches.push(Mismatch::Missing { name: name.clone() }),
+            }
+        }
+        for entry in entries.iter().filter(|entry| is_listed(entry)) {
+            if !listed.contains(entry.name.as_str()) {
+                mismatches.push(Mismatch::Unlisted {
+                    name: entry.name.clone(),
+                });
+            }
+        }
+        Verification { mismatches }
+    }
+}
+
+/// Renders the manifest in the JAR format, with CRLF line breaks and lines wrapped at 72 bytes.
+impl Display for Manifest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Manifest-Version: 1.0\r\n")?;
+        write!(f, "Created-By: chksum-sha1\r\n")?;
+        write!(f, "\r\n")?;
+        for (name, digest) in &self.entries {
+            write_line(f, &format!("Name: {name}"))?;
+            write_line(f, &format!("SHA1-Digest: {}", base64::encode(digest)))?;
+            write!(f, "\r\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// The result of the verification of an archive against its manifest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Verification {
+    mismatches: Vec<Mismatch>,
+}
+
+impl Verification {
+    /// Returns all differences between the manifest and the archive.
+    #[must_use]
+    pub fn mismatches(&self) -> &[Mismatch] {
+        &self.mismatches
+    }
+
+    /// Returns `true` if the archive matches the manifest exactly.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// A difference between the manifest and the archive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Mismatch {
+    /// The data of the entry doesn't match the listed digest.
+    Modified {
+        /// The name of the entry.
+        name: String,
+        /// The digest listed in the manifest.
+        expected: Digest,
+        /// The digest of the entry.
+        computed: Digest,
+    },
+    /// The entry is listed in the manifest, but is missing from the archive.
+    Missing {
+        /// The name of the entry.
+        name: String,
+    },
+    /// The file is present in the archive, but isn't listed in the manifest.
+    Unlisted {
+        /// The name of the entry.
+        name: String,
+    },
+}
+
+impl Mismatch {
+    /// Returns the name of the entry.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Modified { name, .. } | Self::Missing { name } | Self::Unlisted { name } => name,
+        }
+    }
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Modified {
+                name,
+                expected,
+                computed,
+            } => write!(f, "`{name}` has digest {computed}, expected {expected}"),
+            Self::Missing { name } => write!(f, "`{name}` is missing from the archive"),
+            Self::Unlisted { name } => write!(f, "`{name}` is not listed in the manifest"),
+        }
+    }
+}
+
+/// Reads the entries of the archive and, if requested, the data of the manifest.
+fn read(reader: impl Read + Seek, with_manifest: bool) -> Result<(Vec<Entry>, Option<Vec<u8>>)> {
+    let mut archive = ::zip::ZipArchive::new(reader).map_err(io::Error::from)?;
+    let mut entries = Vec::with_capacity(archive.len());
+    let mut manifest = None;
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).map_err(io::Error::from)?;
+        let name = file.name().to_owned();
+        let is_dir = file.is_dir();
+        let mut data = (with_manifest && name == MANIFEST_NAME).then(Vec::new);
+
+        let mut hash = SHA1::new();
+        let mut size = 0;
+        let mut buffer = [0u8; 8 * 1024];
+        loop {
+            let length = match file.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(length) => length,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error.into()),
+            };
+            hash.update(&buffer[..length]);
+            size += length as u64;
+            if let Some(data) = &mut data {
+                data.extend_from_slice(&buffer[..length]);
+            }
+        }
+        let digest = hash.digest();
+
+        if data.is_some() {
+            manifest = data;
+        }
+        entries.push(Entry {
+            name,
+            is_dir,
+            size,
+            digest,
+        });
+    }
+    Ok((entries, manifest))
+}
+
+/// Returns `true` if the entry is expected to be listed in the manifest.
+fn is_listed(entry: &Entry) -> bool {
+    if entry.is_dir || entry.name == MANIFEST_NAME {
+        return false;
+    }
+    // the signature files are located directly in `META-INF`
+    let Some(name) = entry.name.strip_prefix("META-INF/").filter(|name| !name.contains('/')) else {
+        return true;
+    };
+    let name = name.to_ascii_uppercase();
+    let is_signature = name.starts_with("SIG-")
+        || [".SF", ".DSA", ".RSA", ".EC"]
+            .iter()
+            .any(|extension| name.ends_with(extension));
+    !is_signature
+}
+
+/// Splits the manifest into sections of attributes, joining the continuation lines.
+fn sections(manifest: &str) -> Result<Vec<Vec<(&str, String)>>> {
+    let mut sections = Vec::new();
+    let mut section: Vec<(&str, String)> = Vec::new();
+    for line in manifest.split("\r\n").flat_map(|line| line.split(['\r', '\n'])) {
+        if let Some(continuation) = line.strip_prefix(' ') {
+            let (_, value) = section
+                .last_mut()
+                .ok_or_else(|| invalid("manifest continuation line without attribute"))?;
+            value.push_str(continuation);
+        } else if line.is_empty() {
+            if !section.is_empty() {
+                sections.push(section);
+                section = Vec::new();
+            }
+        } else {
+            let (name, value) = line
+                .split_once(": ")
+                .ok_or_else(|| invalid("malformed manifest attribute"))?;
+            section.push((name, value.to_owned()));
+        }
+    }
+    if !section.is_empty() {
+        sections.push(section);
+    }
+    Ok(sections)
+}
+
+/// Returns the value of the attribute, whose name is case-insensitive.
+fn attribute<'a>(section: &'a [(&str, String)], name: &str) -> Option<&'a str> {
+    section
+        .iter()
+        .find(|(attribute, _)| attribute.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// Writes the line, wrapping it into continuation lines which start with a single space.
+fn write_line(f: &mut Formatter<'_>, mut line: &str) -> fmt::Result {
+    let mut limit = LINE_LENGTH;
+    loop {
+        let mut end = line.len().min(limit);
+        // the multi-byte characters are kept intact
+        while !line.is_char_boundary(end) {
+            end -= 1;
+        }
+        write!(f, "{}\r\n", &line[..end])?;
+        line = &line[end..];
+        if line.is_empty() {
+            return Ok(());
+        }
+        write!(f, " ")?;
+        limit = LINE_LENGTH - 1;
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,58 @@
+use chksum_sha1::{self as sha1, batch, Digest};
+
+fn inputs() -> Vec<Vec<u8>> {
+    // lengths around the block and padding boundaries, mixed with longer inputs
+    (0..200)
+        .map(|length| (0..length * 7 % 300).map(|i| (i * 13 + length) as u8).collect())
+        .collect()
+}
+
+#[test]
+fn empty() {
+    let inputs: [&[u8]; 0] = [];
+    let mut digests = [];
+    batch::hash_into(&inputs, &mut digests);
+}
+
+#[test]
+fn same_as_hash() {
+    let inputs = inputs();
+    let mut digests = vec![Digest::new([0; 20]); inputs.len()];
+    batch::hash_into(&inputs, &mut digests);
+    for (input, digest) in inputs.iter().zip(digests) {
+        assert_eq!(digest, sha1::hash(input), "input of length {}", input.len());
+    }
+}
+
+#[test]
+fn uneven_lengths() {
+    let mut inputs = vec![vec![b'a'; 10_000]];
+    inputs.extend((0..20).map(|i| vec![i; i as usize]));
+    inputs.push(vec![b'b'; 3_000]);
+    let mut digests = vec![Digest::new([0; 20]); inputs.len()];
+    batch::hash_into(&inputs, &mut digests);
+    for (input, digest) in inputs.iter().zip(digests) {
+        assert_eq!(digest, sha1::hash(input));
+    }
+
+    let mut digests = [Digest::new([0; 20]); 1];
+    batch::hash_into(&[b"abc"], &mut digests);
+    assert_eq!(digests[0], sha1::hash(b"abc"));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn hash() {
+    let inputs = inputs();
+    let digests = batch::hash(&inputs);
+    assert_eq!(digests.len(), inputs.len());
+    let expected = inputs.iter().map(sha1::hash).collect::<Vec<_>>();
+    assert_eq!(digests, expected);
+}
+
+#[test]
+#[should_panic = "inputs and digests must have the same length"]
+fn length_mismatch() {
+    let mut digests = [Digest::new([0; 20]); 1];
+    batch::hash_into(&[b"a", b"b"], &mut digests);
+}
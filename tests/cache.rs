@@ -0,0 +1,143 @@
+#![cfg(all(feature = "cache", unix))]
+
+use std::fs;
+use std::io::Error as IoError;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, FileWriteStr, PathChild};
+use assert_fs::TempDir;
+use chksum_sha1::cache::Cache;
+use chksum_sha1::{self as sha1, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+/// A digest which doesn't match any file used in the tests.
+const FAKE: &str = "0123456789abcdef0123456789abcdef01234567";
+
+/// Returns a cache file line for the file with the given digest, and its modification time in nanoseconds.
+fn entry(path: &Path, digest: &str) -> Result<(String, i64), Error> {
+    let metadata = fs::metadata(path)?;
+    let modified = metadata.mtime() * 1_000_000_000 + metadata.mtime_nsec();
+    let line = format!(
+        "{} {} {} {modified} {digest}\n",
+        metadata.dev(),
+        metadata.ino(),
+        metadata.size()
+    );
+    Ok((line, modified))
+}
+
+#[test]
+fn missing_cache_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+
+    let cache = Cache::open(temp_dir.child("cache"))?;
+    assert!(cache.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn invalid_cache_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("cache");
+
+    file.write_str("something else\n")?;
+    assert!(matches!(Cache::open(file.path()), Err(ChksumError::Io(_))));
+
+    file.write_str("chksum-sha1 cache 1\n0\n1 2 3 4 xyz\n")?;
+    assert!(matches!(Cache::open(file.path()), Err(ChksumError::Io(_))));
+
+    Ok(())
+}
+
+#[test]
+fn cached_digest() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_str("example data")?;
+
+    let (line, modified) = entry(file.path(), FAKE)?;
+    let written = modified + 10_000_000_000;
+    temp_dir
+        .child("cache")
+        .write_str(&format!("chksum-sha1 cache 1\n{written}\n{line}"))?;
+
+    let mut cache = Cache::open(temp_dir.child("cache"))?;
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.chksum(file.path())?.to_hex_lowercase(), FAKE);
+
+    // the modified file is hashed again
+    file.write_str("other data")?;
+    assert_eq!(cache.chksum(file.path())?, sha1::hash("other data"));
+
+    Ok(())
+}
+
+#[test]
+fn racy_entry() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_str("example data")?;
+
+    // the cache file was written within the safety window after the modification
+    let (line, modified) = entry(file.path(), FAKE)?;
+    let written = modified + 1_000_000_000;
+    temp_dir
+        .child("cache")
+        .write_str(&format!("chksum-sha1 cache 1\n{written}\n{line}"))?;
+
+    let mut cache = Cache::open(temp_dir.child("cache"))?;
+    assert_eq!(cache.chksum(file.path())?, sha1::hash("example data"));
+
+    let mut cache = Cache::open(temp_dir.child("cache"))?;
+    cache.set_safety_window(None);
+    assert_eq!(cache.chksum(file.path())?.to_hex_lowercase(), FAKE);
+
+    Ok(())
+}
+
+#[test]
+fn save() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+    file.write_binary(&[7; 100_000])?;
+
+    // the file has just been written, so its entry is racy
+    let mut cache = Cache::open(temp_dir.child("cache"))?;
+    assert_eq!(cache.chksum(file.path())?, sha1::hash([7; 100_000]));
+    assert_eq!(cache.len(), 1);
+    cache.save()?;
+    assert!(Cache::open(temp_dir.child("cache"))?.is_empty());
+
+    cache.set_safety_window(None);
+    cache.save()?;
+    let mut cache = Cache::open(temp_dir.child("cache"))?;
+    cache.set_safety_window(None);
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.chksum(file.path())?, sha1::hash([7; 100_000]));
+
+    Ok(())
+}
+
+#[test]
+fn directory() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("file.txt").write_str("example data")?;
+
+    let mut cache = Cache::open(temp_dir.child("cache"))?;
+    assert_eq!(cache.chksum(temp_dir.path())?, sha1::chksum(temp_dir.path())?);
+    assert!(cache.is_empty());
+
+    Ok(())
+}
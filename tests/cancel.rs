@@ -0,0 +1,157 @@
+#![cfg(feature = "std")]
+
+use std::error::Error as _;
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild, PathCreateDir};
+use assert_fs::TempDir;
+use chksum_sha1::cancel::{self, Error as CancelError, Token};
+use chksum_sha1::{self as sha1, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Cancel(#[from] CancelError),
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+fn fixture(temp_dir: &TempDir) -> Result<(), Error> {
+    temp_dir.child("b").create_dir_all()?;
+    temp_dir.child("a.bin").write_binary(&[1; 300 * 1024])?;
+    temp_dir.child("b/c.bin").write_binary(b"example data")?;
+    Ok(())
+}
+
+#[test]
+fn not_cancelled() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    fixture(&temp_dir)?;
+
+    let token = Token::new();
+    let digest = cancel::chksum(temp_dir.path(), &token)?;
+    assert_eq!(digest, sha1::chksum(temp_dir.path())?);
+    assert!(!token.is_cancelled());
+
+    Ok(())
+}
+
+#[test]
+fn cancelled() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    fixture(&temp_dir)?;
+
+    let token = Token::new();
+    token.clone().cancel();
+    assert!(token.is_cancelled());
+    let result = cancel::chksum(temp_dir.path(), &token);
+    assert!(matches!(result, Err(CancelError::Cancelled)));
+
+    Ok(())
+}
+
+#[test]
+fn missing_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+
+    let result = cancel::chksum(temp_dir.child("missing"), &Token::new());
+    assert!(matches!(result, Err(CancelError::Chksum(ChksumError::Io(_)))));
+    let error = result.unwrap_err();
+    let source = error.source().and_then(|source| source.downcast_ref::<ChksumError>());
+    assert!(matches!(source, Some(ChksumError::Io(_))));
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn cancelled_from_another_thread() -> Result<(), Error> {
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    use std::process::Command;
+    use std::thread;
+
+    let temp_dir = TempDir::new()?;
+    let fifo = temp_dir.child("fifo");
+    assert!(Command::new("mkfifo").arg(fifo.path()).status()?.success());
+
+    let token = Token::new();
+    let writer = thread::spawn({
+        let token = token.clone();
+        let path = fifo.to_path_buf();
+        move || -> Result<(), IoError> {
+            let mut fifo = OpenOptions::new().write(true).open(path)?;
+            fifo.write_all(b"example")?;
+            token.cancel();
+            // any data read after the cancellation stops the computation, so it never reaches the end of the file;
+            // the reader may have already stopped and closed the pipe, so the result is ignored
+            let _ = fifo.write_all(b" data");
+            Ok(())
+        }
+    });
+    let result = cancel::chksum(fifo.path(), &token);
+    writer.join().unwrap()?;
+    assert!(matches!(result, Err(CancelError::Cancelled)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_runtime_tokio_cancelled() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        fixture(&temp_dir)?;
+
+        let token = Token::new();
+        let digest = cancel::async_chksum(temp_dir.path(), &token).await?;
+        assert_eq!(digest, sha1::chksum(temp_dir.path())?);
+
+        token.cancel();
+        let result = cancel::async_chksum(temp_dir.path(), &token).await;
+        assert!(matches!(result, Err(CancelError::Cancelled)));
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn async_runtime_tokio_cancelled_while_waiting() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        use std::fs::OpenOptions;
+        use std::process::Command;
+        use std::thread;
+        use std::time::Duration;
+
+        let temp_dir = TempDir::new()?;
+        let fifo = temp_dir.child("fifo");
+        assert!(Command::new("mkfifo").arg(fifo.path()).status()?.success());
+
+        // the writer keeps the pipe open without writing anything, so the reading waits for the data forever
+        let writer = thread::spawn({
+            let path = fifo.to_path_buf();
+            move || OpenOptions::new().write(true).open(path)
+        });
+        let token = Token::new();
+        thread::spawn({
+            let token = token.clone();
+            move || {
+                thread::sleep(Duration::from_millis(100));
+                token.cancel();
+            }
+        });
+        let result = cancel::async_chksum(fifo.path(), &token).await;
+        assert!(matches!(result, Err(CancelError::Cancelled)));
+        // closing the pipe ends the abandoned reading, which the runtime waits for on shutdown
+        drop(writer.join().unwrap()?);
+    }
+
+    Ok(())
+}
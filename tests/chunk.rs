@@ -0,0 +1,181 @@
+#![cfg(feature = "std")]
+
+use std::fs::File;
+use std::io::{Error as IoError, Write};
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha1::{chunk, Digest, Error as ChksumError};
+#[cfg(feature = "async-runtime-tokio")]
+use tokio::fs::File as TokioFile;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+fn to_hex(digests: &[Digest]) -> Vec<String> {
+    digests.iter().map(Digest::to_hex_lowercase).collect()
+}
+
+#[test]
+fn empty_data() -> Result<(), Error> {
+    let digests = chunk::chksum(&b""[..], 3)?;
+    assert!(digests.is_empty());
+
+    let digests = chunk::chunks(&b""[..], 3).collect::<Result<Vec<_>, _>>()?;
+    assert!(digests.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn non_empty_data() -> Result<(), Error> {
+    let digests = chunk::chksum(&b"data"[..], 3)?;
+    assert_eq!(
+        to_hex(&digests),
+        [
+            "fbd0b9c3677241899bad77be49dcbb69471a7ef5",
+            "86f7e437faa5a7fce15d1ddcb9eaeaea377667b8"
+        ]
+    );
+
+    let digests = chunk::chksum(&b"data"[..], 4)?;
+    assert_eq!(to_hex(&digests), ["a17c9aaa61e80a1bf71d0d850af4e5baa9800bbd"]);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "chunk size must be greater than zero")]
+fn zero_size() {
+    let _ = chunk::chksum(&b"data"[..], 0);
+}
+
+#[test]
+fn non_empty_file() -> Result<(), Error> {
+    let data = (0..40).flat_map(|_| 0..=255u8).collect::<Vec<_>>();
+    let temp_dir = TempDir::new()?;
+    let child = {
+        let file = temp_dir.child("file.bin");
+        file.touch()?;
+        file.write_binary(&data)?;
+        file
+    };
+
+    let digests = chunk::chksum_path(child.path(), 1000)?;
+    assert_eq!(digests.len(), 11);
+    assert_eq!(
+        to_hex(&digests[9..]),
+        [
+            "2bf17465c1649beeb2bc055c3713b434d4d18f66",
+            "9f3cff86bee00c2ad05b1c295888a444aca0eca1"
+        ]
+    );
+
+    let digests = chunk::chksum(File::open(child.path())?, 4096)?;
+    assert_eq!(digests.len(), 3);
+    assert_eq!(
+        to_hex(&digests[1..]),
+        [
+            "e9dded8c84614e894501965af60c2525794a8c7d",
+            "f10ccfde60c17db26e7d85d35665c7661dbbeb2c"
+        ]
+    );
+
+    let digests = chunk::chunks(File::open(child.path())?, 4096).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(digests.len(), 3);
+    assert_eq!(
+        to_hex(&digests[1..]),
+        [
+            "e9dded8c84614e894501965af60c2525794a8c7d",
+            "f10ccfde60c17db26e7d85d35665c7661dbbeb2c"
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn directory() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("a.txt").write_binary(b"da")?;
+    temp_dir.child("b/c.txt").write_binary(b"ta")?;
+
+    // the chunks span the files, the same way as the whole directory is hashed
+    let digests = chunk::chksum_path(temp_dir.path(), 3)?;
+    assert_eq!(
+        to_hex(&digests),
+        [
+            "fbd0b9c3677241899bad77be49dcbb69471a7ef5",
+            "86f7e437faa5a7fce15d1ddcb9eaeaea377667b8"
+        ]
+    );
+
+    let result = chunk::chksum_path(temp_dir.child("missing"), 3);
+    assert!(matches!(result, Err(ChksumError::Io(_))));
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "async-runtime-tokio"), ignore)]
+#[tokio::test]
+async fn async_runtime_tokio_non_empty_file_as_file() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let child = {
+            let file = temp_dir.child("file.txt");
+            file.touch()?;
+            file.write_binary(b"data")?;
+            file
+        };
+
+        let file = TokioFile::open(child.path()).await?;
+        let digests = chunk::async_chksum(file, 3).await?;
+        assert_eq!(
+            to_hex(&digests),
+            [
+                "fbd0b9c3677241899bad77be49dcbb69471a7ef5",
+                "86f7e437faa5a7fce15d1ddcb9eaeaea377667b8"
+            ]
+        );
+
+        let digests = chunk::async_chksum_path(temp_dir.path(), 3).await?;
+        assert_eq!(
+            to_hex(&digests),
+            [
+                "fbd0b9c3677241899bad77be49dcbb69471a7ef5",
+                "86f7e437faa5a7fce15d1ddcb9eaeaea377667b8"
+            ]
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn writer() -> Result<(), Error> {
+    let mut digests = Vec::new();
+    let mut writer = chunk::writer(Vec::new(), 3, |digest| digests.push(digest));
+    writer.write_all(b"d")?;
+    writer.write_all(b"at")?;
+    writer.write_all(b"a")?;
+    let inner = writer.finish();
+    assert_eq!(inner, b"data");
+    assert_eq!(
+        to_hex(&digests),
+        [
+            "fbd0b9c3677241899bad77be49dcbb69471a7ef5",
+            "86f7e437faa5a7fce15d1ddcb9eaeaea377667b8"
+        ]
+    );
+
+    Ok(())
+}
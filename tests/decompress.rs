@@ -0,0 +1,138 @@
+#![cfg(any(feature = "gzip", feature = "xz", feature = "zstd"))]
+
+use std::io::{Cursor, Error as IoError};
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, FileWriteStr, PathChild, PathCreateDir};
+use assert_fs::TempDir;
+use chksum_sha1::decompress::{self, Decompressed, Format};
+use chksum_sha1::{self as sha1, Error as ChksumError, Operation, PathError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+/// Compresses the data in the format, appending a second member, frame or stream with the rest of the data.
+fn compress(format: Format, data: &[u8]) -> Result<Vec<u8>, IoError> {
+    let (first, second) = data.split_at(data.len() / 2);
+    let mut compressed = Vec::new();
+    for data in [first, second] {
+        match format {
+            #[cfg(feature = "gzip")]
+            Format::Gzip => {
+                use std::io::Write;
+
+                let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                compressed.extend(encoder.finish()?);
+            },
+            #[cfg(feature = "zstd")]
+            Format::Zstd => compressed.extend(zstd::encode_all(data, 0)?),
+            #[cfg(feature = "xz")]
+            Format::Xz => {
+                use std::io::Read;
+
+                let mut encoder = liblzma::read::XzEncoder::new(data, 6);
+                encoder.read_to_end(&mut compressed)?;
+            },
+        }
+    }
+    Ok(compressed)
+}
+
+/// Returns all formats enabled by Cargo features.
+fn formats() -> Vec<Format> {
+    vec![
+        #[cfg(feature = "gzip")]
+        Format::Gzip,
+        #[cfg(feature = "zstd")]
+        Format::Zstd,
+        #[cfg(feature = "xz")]
+        Format::Xz,
+    ]
+}
+
+#[test]
+fn reader() -> Result<(), Error> {
+    let data = "example data".repeat(1000);
+    for format in formats() {
+        let compressed = compress(format, data.as_bytes())?;
+        assert_eq!(Format::detect(&compressed), Some(format));
+
+        let digest = sha1::chksum(Decompressed::new(Cursor::new(&compressed)))?;
+        assert_eq!(digest, sha1::hash(&data), "{format:?}");
+        let digest = sha1::chksum(Decompressed::with_format(Cursor::new(&compressed), format))?;
+        assert_eq!(digest, sha1::hash(&data), "{format:?}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn uncompressed() -> Result<(), Error> {
+    for data in ["", "e", "example data"] {
+        let digest = sha1::chksum(Decompressed::new(data.as_bytes()))?;
+        assert_eq!(digest, sha1::hash(data));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn invalid_data() {
+    for format in formats() {
+        let result = sha1::chksum(Decompressed::with_format(&b"example data"[..], format));
+        assert!(result.is_err(), "{format:?}");
+    }
+}
+
+#[test]
+fn path() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("a").create_dir_all()?;
+    temp_dir.child("a/plain.txt").write_str("example")?;
+    for (index, format) in formats().into_iter().enumerate() {
+        let compressed = compress(format, b" data")?;
+        temp_dir.child(format!("b{index}")).write_binary(&compressed)?;
+
+        let digest = decompress::chksum(temp_dir.child(format!("b{index}")), Some(format))?;
+        assert_eq!(digest, sha1::hash(" data"));
+    }
+
+    let digest = decompress::chksum(temp_dir.path(), None)?;
+    assert_eq!(
+        digest,
+        sha1::hash(format!("example{}", " data".repeat(formats().len())))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn truncated_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    for format in formats() {
+        let compressed = compress(format, "example data".repeat(100).as_bytes())?;
+        let file = temp_dir.child("truncated");
+        file.write_binary(&compressed[..compressed.len() / 4])?;
+
+        let error = decompress::chksum(file.path(), None).unwrap_err();
+        let ChksumError::Io(error) = error else {
+            panic!("error must be an I/O error");
+        };
+        let error = error
+            .get_ref()
+            .and_then(|error| error.downcast_ref::<PathError>())
+            .unwrap();
+        assert_eq!(error.operation(), Operation::Read, "{format:?}");
+        assert_eq!(error.path(), file.path());
+    }
+
+    Ok(())
+}
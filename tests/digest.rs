@@ -0,0 +1,67 @@
+#![cfg(feature = "digest")]
+
+use chksum_sha1::digest::const_oid::AssociatedOid;
+use chksum_sha1::digest::{Digest as _, FixedOutputReset, Mac};
+use chksum_sha1::SHA1;
+use hmac::SimpleHmac;
+use rsa::Pkcs1v15Sign;
+
+#[test]
+fn digest() {
+    let digest = <SHA1 as chksum_sha1::digest::Digest>::digest(b"abc");
+    assert_eq!(digest.as_slice(), SHA1::hash(b"abc").as_ref());
+
+    let mut hash = <SHA1 as chksum_sha1::digest::Digest>::new();
+    chksum_sha1::digest::Digest::update(&mut hash, b"example");
+    chksum_sha1::digest::Digest::update(&mut hash, b" data");
+    let digest = hash.finalize();
+    assert_eq!(digest.as_slice(), SHA1::hash(b"example data").as_ref());
+}
+
+#[test]
+fn finalize_reset() {
+    let mut hash = SHA1::new();
+    hash.update(b"example data");
+    let digest = hash.finalize_fixed_reset();
+    assert_eq!(digest.as_slice(), SHA1::hash(b"example data").as_ref());
+    assert_eq!(hash, SHA1::new());
+}
+
+#[test]
+fn hmac() {
+    // RFC 2202, test cases 1 and 6
+    let mut mac = SimpleHmac::<SHA1>::new_from_slice(&[0x0B; 20]).unwrap();
+    mac.update(b"Hi There");
+    let code = mac.finalize().into_bytes();
+    assert_eq!(
+        code.as_slice(),
+        [
+            0xB6, 0x17, 0x31, 0x86, 0x55, 0x05, 0x72, 0x64, 0xE2, 0x8B, 0xC0, 0xB6, 0xFB, 0x37, 0x8C, 0x8E, 0xF1, 0x46,
+            0xBE, 0x00
+        ]
+    );
+
+    let mut mac = SimpleHmac::<SHA1>::new_from_slice(&[0xAA; 80]).unwrap();
+    mac.update(b"Test Using Larger Than Block-Size Key - Hash Key First");
+    let code = mac.finalize().into_bytes();
+    assert_eq!(
+        code.as_slice(),
+        [
+            0xAA, 0x4A, 0xE5, 0xE1, 0x52, 0x72, 0xD0, 0x0E, 0x95, 0x70, 0x56, 0x37, 0xCE, 0x8A, 0x3B, 0x55, 0xED, 0x40,
+            0x21, 0x12
+        ]
+    );
+}
+
+#[test]
+fn oid() {
+    assert_eq!(SHA1::OID.to_string(), "1.3.14.3.2.26");
+
+    // the DER encoding of `DigestInfo` of RFC 8017 preceding the digest
+    let scheme = Pkcs1v15Sign::new::<SHA1>();
+    assert_eq!(scheme.hash_len, Some(20));
+    assert_eq!(
+        scheme.prefix.as_ref(),
+        [0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2B, 0x0E, 0x03, 0x02, 0x1A, 0x05, 0x00, 0x04, 0x14]
+    );
+}
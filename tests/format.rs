@@ -0,0 +1,36 @@
+use chksum_sha1::{Digest, SHA1};
+
+#[test]
+fn format() {
+    let digest = SHA1::hash(b"abc");
+    assert_eq!(format!("{digest}"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+    assert_eq!(format!("{digest:x}"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+    assert_eq!(format!("{digest:X}"), "A9993E364706816ABA3E25717850C26C9CD0D89D");
+    assert_eq!(format!("{digest:#x}"), "0xa9993e364706816aba3e25717850c26c9cd0d89d");
+    assert_eq!(format!("{digest:#X}"), "0XA9993E364706816ABA3E25717850C26C9CD0D89D");
+    assert_eq!(format!("{digest:>42}"), "  a9993e364706816aba3e25717850c26c9cd0d89d");
+    assert_eq!(
+        format!("{digest:#044x}"),
+        "0x00a9993e364706816aba3e25717850c26c9cd0d89d"
+    );
+}
+
+#[test]
+fn conversions() {
+    let bytes = [
+        0xA9, 0x99, 0x3E, 0x36, 0x47, 0x06, 0x81, 0x6A, 0xBA, 0x3E, 0x25, 0x71, 0x78, 0x50, 0xC2, 0x6C, 0x9C, 0xD0,
+        0xD8, 0x9D,
+    ];
+    let digest = Digest::from(bytes);
+    assert_eq!(digest, chksum_sha1::hash(b"abc"));
+    assert_eq!(digest.as_bytes(), bytes);
+    assert_eq!(digest.into_inner(), bytes);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn to_hex() {
+    let digest = SHA1::hash(b"abc");
+    assert_eq!(digest.to_hex_lowercase(), "a9993e364706816aba3e25717850c26c9cd0d89d");
+    assert_eq!(digest.to_hex_uppercase(), "A9993E364706816ABA3E25717850C26C9CD0D89D");
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "alloc")]
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher as _, Hash, Hasher as _};
+
+use chksum_sha1::hasher::{self, BuildHasher, Hasher, StableHash};
+use chksum_sha1::SHA1;
+
+struct Key {
+    id: u64,
+    name: String,
+    size: usize,
+    offset: isize,
+}
+
+impl StableHash for Key {
+    fn stable_hash(&self, hash: &mut SHA1) {
+        self.id.stable_hash(hash);
+        self.name.stable_hash(hash);
+        self.size.stable_hash(hash);
+        self.offset.stable_hash(hash);
+    }
+}
+
+#[test]
+fn encoding() {
+    let key = Key {
+        id: 1,
+        name: String::from("example"),
+        size: 2,
+        offset: -1,
+    };
+    let mut data = Vec::new();
+    data.extend(1u64.to_le_bytes());
+    data.extend(7u64.to_le_bytes());
+    data.extend(b"example");
+    data.extend(2u64.to_le_bytes());
+    data.extend((-1i64).to_le_bytes());
+    assert_eq!(hasher::hash_value(&key), SHA1::hash(data));
+
+    let value = (true, 'a', Some(0x0102u16), None::<u8>, [-2i32], vec![3u128; 2], ());
+    let mut data = vec![1];
+    data.extend(0x61u32.to_le_bytes());
+    data.push(1);
+    data.extend(0x0102u16.to_le_bytes());
+    data.push(0);
+    data.extend(1u64.to_le_bytes());
+    data.extend((-2i32).to_le_bytes());
+    data.extend(2u64.to_le_bytes());
+    data.extend(3u128.to_le_bytes());
+    data.extend(3u128.to_le_bytes());
+    assert_eq!(hasher::hash_value(&value), SHA1::hash(data));
+}
+
+#[test]
+fn unambiguous() {
+    assert_ne!(hasher::hash_value(&("ab", "c")), hasher::hash_value(&("a", "bc")));
+    assert_ne!(
+        hasher::hash_value(&[[1u8], [2]][..]),
+        hasher::hash_value(&[[1u8, 2]][..])
+    );
+    assert_eq!(
+        hasher::hash_value("example"),
+        hasher::hash_value(&String::from("example"))
+    );
+    assert_eq!(hasher::hash_value(&[1u8, 2][..]), hasher::hash_value(&vec![1u8, 2]));
+}
+
+#[test]
+fn hasher_encoding() {
+    let mut hasher = Hasher::new();
+    hasher.write(b"example");
+    hasher.write_u8(1);
+    hasher.write_u16(0x0102);
+    hasher.write_i32(-2);
+    hasher.write_u64(3);
+    hasher.write_u128(4);
+    hasher.write_usize(5);
+    hasher.write_isize(-6);
+    let mut data = Vec::new();
+    data.extend(b"example");
+    data.extend(1u8.to_le_bytes());
+    data.extend(0x0102u16.to_le_bytes());
+    data.extend((-2i32).to_le_bytes());
+    data.extend(3u64.to_le_bytes());
+    data.extend(4u128.to_le_bytes());
+    data.extend(5u64.to_le_bytes());
+    data.extend((-6i64).to_le_bytes());
+    assert_eq!(hasher.digest(), SHA1::hash(data));
+}
+
+#[test]
+fn finish() {
+    let mut hasher = Hasher::new();
+    hasher.write(b"abc");
+    assert_eq!(hasher.finish(), 0xA999_3E36_4706_816A);
+    assert_eq!(
+        format!("{:x}", hasher.digest()),
+        "a9993e364706816aba3e25717850c26c9cd0d89d"
+    );
+}
+
+#[test]
+fn build_hasher() {
+    let mut hasher = BuildHasher.build_hasher();
+    "example".hash(&mut hasher);
+    let mut expected = Hasher::new();
+    "example".hash(&mut expected);
+    assert_eq!(hasher, expected);
+    assert_eq!(BuildHasher.build_hasher(), Hasher::new());
+
+    let mut map = HashMap::with_hasher(BuildHasher);
+    map.insert(String::from("key"), 1);
+    assert_eq!(map.get("key"), Some(&1));
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "alloc")]
+
+use chksum_sha1::{self as sha1, http};
+
+#[test]
+fn digest_field() {
+    let digest = sha1::hash("example data");
+    let value = http::digest_field(digest);
+    assert_eq!(value, "SHA=n8Qq2sMTA9aLRE5hKfE/YJOg4EU=");
+    assert_eq!(http::parse_digest_field(&value), Some(digest));
+
+    let value = "MD5=HUXZLQLMuI/KZ5KDcJPcOA==, sha=n8Qq2sMTA9aLRE5hKfE/YJOg4EU=";
+    assert_eq!(http::parse_digest_field(value), Some(digest));
+    let value = " UNIXsum=30637 ,  SHA = n8Qq2sMTA9aLRE5hKfE/YJOg4EU= ";
+    assert_eq!(http::parse_digest_field(value), Some(digest));
+}
+
+#[test]
+fn invalid_digest_field() {
+    let values = [
+        "",
+        "MD5=HUXZLQLMuI/KZ5KDcJPcOA==",
+        "SHA-256=n8Qq2sMTA9aLRE5hKfE/YJOg4EU=",
+        "SHA=HUXZLQLMuI/KZ5KDcJPcOA==",
+        "SHA=n8Qq2sMTA9aLRE5hKfE/YJOg4EU",
+        "SHA",
+    ];
+    for value in values {
+        assert_eq!(http::parse_digest_field(value), None, "{value}");
+    }
+}
+
+#[test]
+fn want_digest_field() {
+    assert_eq!(http::parse_want_digest_field("SHA"), Some(1.0));
+    assert_eq!(http::parse_want_digest_field("MD5;q=0.3, sha;q=1"), Some(1.0));
+    assert_eq!(http::parse_want_digest_field("SHA-256, SHA ; q=0.5"), Some(0.5));
+    assert_eq!(http::parse_want_digest_field("SHA;q=0"), None);
+    assert_eq!(http::parse_want_digest_field("SHA;q=1.5"), None);
+    assert_eq!(http::parse_want_digest_field("SHA-512, MD5"), None);
+}
+
+#[test]
+fn repr_digest_field() {
+    let digest = sha1::hash("example data");
+    let value = http::repr_digest_field(digest);
+    assert_eq!(value, "sha=:n8Qq2sMTA9aLRE5hKfE/YJOg4EU=:");
+    assert_eq!(http::parse_repr_digest_field(&value), Some(digest));
+
+    let value = "sha-512=:YMAam51Jz/jOATT6/zvHrLVgOYTGFy1d6GJiOHTohq4yP+pgk4vf2aCsyRZOtw8MjkM7iw7yZ/WkppmM44T3qg==:, \
+                 sha=:n8Qq2sMTA9aLRE5hKfE/YJOg4EU=:;param";
+    assert_eq!(http::parse_repr_digest_field(value), Some(digest));
+
+    // the last member with the same key wins
+    let value = "sha=:AAAAAAAAAAAAAAAAAAAAAAAAAAA=:, sha=:n8Qq2sMTA9aLRE5hKfE/YJOg4EU=:";
+    assert_eq!(http::parse_repr_digest_field(value), Some(digest));
+}
+
+#[test]
+fn invalid_repr_digest_field() {
+    let values = [
+        "",
+        "SHA=:n8Qq2sMTA9aLRE5hKfE/YJOg4EU=:",
+        "sha=n8Qq2sMTA9aLRE5hKfE/YJOg4EU=",
+        "sha=:n8Qq2sMTA9aLRE5hKfE/YJOg4EU=",
+        "sha=:n8Qq2sMTA9aLRE5hKfE/YJOg4EU=:, sha=?1",
+        "sha=:n8Qq2sMTA9aLRE5hKfE/YJOg4EU=:, sha",
+        "sha=:n8Qq2sMTA9aLRE5hKfE/YJOg4EU=:, sha;a=1",
+        "sha",
+    ];
+    for value in values {
+        assert_eq!(http::parse_repr_digest_field(value), None, "{value}");
+    }
+}
+
+#[test]
+fn want_repr_digest_field() {
+    assert_eq!(http::parse_want_repr_digest_field("sha=10"), Some(10));
+    assert_eq!(http::parse_want_repr_digest_field("sha-256=10, sha=1"), Some(1));
+    assert_eq!(http::parse_want_repr_digest_field("sha=0"), None);
+    assert_eq!(http::parse_want_repr_digest_field("sha=11"), None);
+    assert_eq!(http::parse_want_repr_digest_field("sha=10, sha"), None);
+    assert_eq!(http::parse_want_repr_digest_field("sha-256=10"), None);
+}
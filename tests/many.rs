@@ -0,0 +1,105 @@
+#![cfg(feature = "std")]
+
+use std::path::PathBuf;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha1::{self as sha1, many, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+}
+
+/// The paths of the files with their contents, if they exist.
+type Files = Vec<(PathBuf, Option<Vec<u8>>)>;
+
+/// Creates files with different contents, every third of them missing.
+fn fixture(temp_dir: &TempDir) -> Result<Files, Error> {
+    (0..50)
+        .map(|i| {
+            let file = temp_dir.child(format!("file-{i}.bin"));
+            if i % 3 == 2 {
+                return Ok((file.to_path_buf(), None));
+            }
+            let data = vec![i as u8; i * 1000];
+            file.write_binary(&data)?;
+            Ok((file.to_path_buf(), Some(data)))
+        })
+        .collect()
+}
+
+#[test]
+fn empty() {
+    let results = many::chksum(Vec::<PathBuf>::new(), 4);
+    assert!(results.is_empty());
+}
+
+#[test]
+fn paths() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let files = fixture(&temp_dir)?;
+
+    for concurrency in [1, 4, 100] {
+        let results = many::chksum(files.iter().map(|(path, _)| path), concurrency);
+        assert_eq!(results.len(), files.len());
+        for ((_, data), result) in files.iter().zip(results) {
+            match data {
+                Some(data) => assert_eq!(result?, sha1::hash(data)),
+                None => assert!(matches!(result, Err(ChksumError::Io(_)))),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn bytes() {
+    let results = many::chksum(["example", " ", "data"], 2);
+    assert_eq!(results.len(), 3);
+    assert_eq!(
+        results[0].as_ref().unwrap().to_hex_lowercase(),
+        "c3499c2729730a7f807efb8676a92dcb6f8a3f8f"
+    );
+}
+
+#[test]
+fn concurrency_above_inputs() {
+    // no more threads than inputs are spawned, so the bound cannot exhaust the system
+    let results = many::chksum(["example", "data"], usize::MAX);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap(), &sha1::hash("example"));
+}
+
+#[test]
+#[should_panic(expected = "concurrency must be greater than zero")]
+fn zero_concurrency() {
+    let _ = many::chksum(["example"], 0);
+}
+
+#[tokio::test]
+async fn async_runtime_tokio_paths() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let files = fixture(&temp_dir)?;
+
+        for concurrency in [1, 4, 100] {
+            let results = many::async_chksum(files.iter().map(|(path, _)| path.clone()), concurrency).await;
+            assert_eq!(results.len(), files.len());
+            for ((_, data), result) in files.iter().zip(results) {
+                match data {
+                    Some(data) => assert_eq!(result?, sha1::hash(data)),
+                    None => assert!(matches!(result, Err(ChksumError::Io(_)))),
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "mmap")]
+
+use std::fs::File;
+use std::io::{Error as IoError, Seek, SeekFrom};
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha1::{self as sha1, mmap, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+// the files hashed by the tests are never truncated concurrently, which is the safety contract of `mmap::chksum`
+
+#[test]
+fn empty_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.touch()?;
+
+    let digest = unsafe { mmap::chksum(&File::open(file.path())?)? };
+    assert_eq!(digest.to_hex_lowercase(), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+
+    Ok(())
+}
+
+#[test]
+fn file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.bin");
+    file.touch()?;
+    let data = (0..3 * 1024 * 1024).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    file.write_binary(&data)?;
+
+    let mut handle = File::open(file.path())?;
+    let digest = unsafe { mmap::chksum(&handle)? };
+    assert_eq!(digest, sha1::hash(&data));
+    assert_eq!(handle.stream_position()?, data.len() as u64);
+    assert_eq!(unsafe { mmap::chksum(&handle)? }, sha1::hash(b""));
+
+    Ok(())
+}
+
+#[test]
+fn file_with_position() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.touch()?;
+    file.write_binary(b"example data")?;
+
+    let mut handle = File::open(file.path())?;
+    handle.seek(SeekFrom::Start(8))?;
+    let digest = unsafe { mmap::chksum(&handle)? };
+    assert_eq!(digest, sha1::hash(b"data"));
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn non_regular_file() -> Result<(), Error> {
+    let digest = unsafe { mmap::chksum(&File::open("/dev/null")?)? };
+    assert_eq!(digest.to_hex_lowercase(), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+
+    // reports zero length but isn't empty
+    let digest = unsafe { mmap::chksum(&File::open("/proc/self/cmdline")?)? };
+    assert_ne!(digest, sha1::hash(b""));
+
+    Ok(())
+}
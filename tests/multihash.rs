@@ -0,0 +1,132 @@
+use chksum_sha1 as sha1;
+#[cfg(feature = "alloc")]
+use chksum_sha1::multihash::Base;
+use chksum_sha1::multihash::{Error, MULTIHASH_LENGTH_BYTES};
+use chksum_sha1::Digest;
+
+#[test]
+fn encode() {
+    let digest = sha1::hash("example");
+    let multihash = sha1::multihash::encode(digest);
+    assert_eq!(multihash.len(), MULTIHASH_LENGTH_BYTES);
+    assert_eq!(multihash[..2], [0x11, 0x14]);
+    assert_eq!(multihash[2..], *digest.as_bytes());
+}
+
+#[test]
+fn decode() {
+    let digest = Digest::new([0xAB; 20]);
+    let multihash = sha1::multihash::encode(digest);
+    assert_eq!(sha1::multihash::decode(&multihash), Ok(digest));
+
+    // SHA2-256
+    let multihash = [[0x12, 0x20].as_slice(), &[0u8; 32]].concat();
+    assert_eq!(sha1::multihash::decode(&multihash), Err(Error::UnsupportedCode(0x12)));
+    // a code which takes two bytes of varint
+    let multihash = [[0x80, 0x01, 0x14].as_slice(), &[0u8; 20]].concat();
+    assert_eq!(sha1::multihash::decode(&multihash), Err(Error::UnsupportedCode(0x80)));
+    // truncated digest
+    assert_eq!(
+        sha1::multihash::decode(&[0x11, 0x14, 0x00]),
+        Err(Error::InvalidLength { value: 1, proper: 20 })
+    );
+    // trailing bytes
+    let multihash = [[0x11, 0x14].as_slice(), &[0u8; 21]].concat();
+    assert_eq!(
+        sha1::multihash::decode(&multihash),
+        Err(Error::InvalidLength { value: 21, proper: 20 })
+    );
+    // truncated digest length
+    let multihash = [[0x11, 0x0A].as_slice(), &[0u8; 10]].concat();
+    assert_eq!(
+        sha1::multihash::decode(&multihash),
+        Err(Error::InvalidLength { value: 10, proper: 20 })
+    );
+}
+
+#[test]
+fn decode_invalid_varint() {
+    let multihashes: [&[u8]; 5] = [
+        &[],
+        &[0x91],
+        &[0x11],
+        // not minimal
+        &[0x91, 0x00, 0x14],
+        // longer than 9 bytes
+        &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01],
+    ];
+    for multihash in multihashes {
+        assert_eq!(
+            sha1::multihash::decode(multihash),
+            Err(Error::InvalidVarint),
+            "{multihash:?}"
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn multibase() {
+    let digest = sha1::hash("example");
+    let multibases = [
+        (Base::Base16, "f1114c3499c2729730a7f807efb8676a92dcb6f8a3f8f"),
+        (Base::Base16Upper, "F1114C3499C2729730A7F807EFB8676A92DCB6F8A3F8F"),
+        (Base::Base32, "bcekmgsm4e4uxgct7qb7pxbtwvew4w34kh6hq"),
+        (Base::Base32Upper, "BCEKMGSM4E4UXGCT7QB7PXBTWVEW4W34KH6HQ"),
+        (Base::Base58Btc, "z5dtWDC1D6ojWEKDxZfAPUk95op9Bvv"),
+        (Base::Base64, "mERTDSZwnKXMKf4B++4Z2qS3Lb4o/jw"),
+        (Base::Base64Pad, "MERTDSZwnKXMKf4B++4Z2qS3Lb4o/jw=="),
+        (Base::Base64Url, "uERTDSZwnKXMKf4B--4Z2qS3Lb4o_jw"),
+        (Base::Base64UrlPad, "UERTDSZwnKXMKf4B--4Z2qS3Lb4o_jw=="),
+    ];
+    for (base, expected) in multibases {
+        assert_eq!(sha1::multihash::encode_multibase(digest, base), expected);
+        assert_eq!(sha1::multihash::decode_multibase(expected), Ok(digest));
+        assert_eq!(Base::from_prefix(base.prefix()), Some(base));
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn multibase_leading_zeros() {
+    // the multihash starts with a nonzero byte, so only the digest can have leading zeros
+    let digest = Digest::new([0; 20]);
+    let multibase = sha1::multihash::encode_multibase(digest, Base::Base58Btc);
+    assert_eq!(sha1::multihash::decode_multibase(&multibase), Ok(digest));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn decode_multibase_invalid() {
+    assert_eq!(sha1::multihash::decode_multibase(""), Err(Error::InvalidEncoding));
+    assert_eq!(
+        sha1::multihash::decode_multibase("k1114"),
+        Err(Error::UnsupportedBase('k'))
+    );
+    let multibases = [
+        // uppercase digits in lowercase base
+        "f1114C3499C2729730A7F807EFB8676A92DCB6F8A3F8F",
+        // odd number of digits
+        "f1114c3499c2729730a7f807efb8676a92dcb6f8a3f8",
+        // zero is not in the alphabet
+        "z0dtWDC1D6ojWEKDxZfAPUk95op9Bvv",
+        // nonzero trailing bits
+        "bcekmgsm4e4uxgct7qb7pxbtwvew4w34kh6hr",
+        // missing padding
+        "MERTDSZwnKXMKf4B++4Z2qS3Lb4o/jw",
+        // standard alphabet in URL-safe base
+        "uERTDSZwnKXMKf4B++4Z2qS3Lb4o/jw",
+    ];
+    for multibase in multibases {
+        assert_eq!(
+            sha1::multihash::decode_multibase(multibase),
+            Err(Error::InvalidEncoding),
+            "{multibase}"
+        );
+    }
+    // valid encoding of an invalid multihash
+    assert_eq!(
+        sha1::multihash::decode_multibase("f1220"),
+        Err(Error::UnsupportedCode(0x12))
+    );
+}
@@ -0,0 +1,121 @@
+#![cfg(feature = "std")]
+
+use std::io::Error as IoError;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteStr, PathChild, PathCreateDir};
+use assert_fs::TempDir;
+use chksum_sha1::{self as sha1, path, Error as ChksumError, Operation, PathError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[error(transparent)]
+    Path(#[from] PathError),
+}
+
+#[test]
+fn directory() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("b").create_dir_all()?;
+    temp_dir.child("a.txt").write_str("example")?;
+    temp_dir.child("b/c.txt").write_str(" data")?;
+
+    let digest = path::chksum(temp_dir.path())?;
+    assert_eq!(digest, sha1::chksum(temp_dir.path())?);
+    assert_eq!(digest, sha1::hash("example data"));
+
+    let outcome = path::chksum_keep_going(temp_dir.path());
+    assert!(outcome.is_complete());
+    assert_eq!(outcome.digest(), digest);
+
+    Ok(())
+}
+
+#[test]
+fn missing_path() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let missing = temp_dir.child("missing");
+
+    let error = path::chksum(missing.path()).unwrap_err();
+    assert_eq!(error.operation(), Operation::Metadata);
+    assert_eq!(error.path(), missing.path());
+    assert!(matches!(error.error(), ChksumError::Io(_)));
+    assert!(error.to_string().contains("missing"));
+
+    let outcome = path::chksum_keep_going(missing.path());
+    assert_eq!(outcome.errors().len(), 1);
+    assert_eq!(outcome.digest(), sha1::hash(""));
+
+    Ok(())
+}
+
+#[test]
+fn context_in_io_error() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let missing = temp_dir.child("missing");
+
+    let error: ChksumError = path::chksum(missing.path()).unwrap_err().into();
+    let ChksumError::Io(error) = error else {
+        panic!("error must be an I/O error");
+    };
+    let error = error
+        .get_ref()
+        .and_then(|error| error.downcast_ref::<PathError>())
+        .unwrap();
+    assert_eq!(error.path(), missing.path());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn keep_going() -> Result<(), Error> {
+    use std::os::unix::fs::symlink;
+
+    let temp_dir = TempDir::new()?;
+    temp_dir.child("b").create_dir_all()?;
+    temp_dir.child("a.txt").write_str("example")?;
+    symlink(temp_dir.child("missing"), temp_dir.child("b/broken"))?;
+    temp_dir.child("c.txt").write_str(" data")?;
+
+    let error = path::chksum(temp_dir.path()).unwrap_err();
+    assert_eq!(error.operation(), Operation::Metadata);
+    assert_eq!(error.path(), temp_dir.child("b/broken").path());
+
+    let outcome = path::chksum_keep_going(temp_dir.path());
+    assert!(!outcome.is_complete());
+    assert_eq!(outcome.digest(), sha1::hash("example data"));
+    let (_, errors) = outcome.into_parts();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].path(), temp_dir.child("b/broken").path());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_runtime_tokio_keep_going() -> Result<(), Error> {
+    #[cfg(all(feature = "async-runtime-tokio", unix))]
+    {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new()?;
+        temp_dir.child("a.txt").write_str("example")?;
+        symlink(temp_dir.child("missing"), temp_dir.child("b"))?;
+        temp_dir.child("c.txt").write_str(" data")?;
+
+        let error = path::async_chksum(temp_dir.path()).await.unwrap_err();
+        assert_eq!(error.path(), temp_dir.child("b").path());
+
+        let outcome = path::async_chksum_keep_going(temp_dir.path()).await;
+        assert_eq!(outcome.errors().len(), 1);
+        assert_eq!(outcome.digest(), sha1::hash("example data"));
+    }
+
+    Ok(())
+}
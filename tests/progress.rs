@@ -0,0 +1,149 @@
+#![cfg(feature = "std")]
+
+use std::io::Error as IoError;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteBin, PathChild, PathCreateDir};
+use assert_fs::TempDir;
+use chksum_sha1::{self as sha1, progress, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+/// Creates a directory tree with files of different lengths, returning their total length.
+fn fixture(temp_dir: &TempDir) -> Result<u64, Error> {
+    temp_dir.child("b").create_dir_all()?;
+    temp_dir.child("a.bin").write_binary(&[1; 300 * 1024])?;
+    temp_dir.child("b/c.bin").write_binary(&[2; 100])?;
+    temp_dir.child("b/d.bin").write_binary(&[3; 200 * 1024])?;
+    temp_dir.child("e.bin").write_binary(b"")?;
+    Ok(500 * 1024 + 100)
+}
+
+#[test]
+fn empty_directory() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+
+    let mut reports = Vec::new();
+    let digest = progress::chksum(temp_dir.path(), Duration::ZERO, |progress| {
+        reports.push((
+            progress.processed(),
+            progress.total(),
+            progress.path().map(PathBuf::from),
+        ));
+    })?;
+    assert_eq!(digest, sha1::chksum(temp_dir.path())?);
+    assert_eq!(reports, [(0, Some(0), None)]);
+
+    Ok(())
+}
+
+#[test]
+fn directory() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let total = fixture(&temp_dir)?;
+
+    let mut reports = Vec::new();
+    let digest = progress::chksum(temp_dir.path(), Duration::ZERO, |progress| {
+        reports.push((
+            progress.processed(),
+            progress.total(),
+            progress.path().map(PathBuf::from),
+        ));
+    })?;
+    assert_eq!(digest, sha1::chksum(temp_dir.path())?);
+
+    assert!(reports.len() > 4);
+    assert!(reports.windows(2).all(|reports| reports[0].0 <= reports[1].0));
+    assert!(reports.iter().all(|(_, report_total, _)| *report_total == Some(total)));
+    assert_eq!(reports.first().unwrap().2, Some(temp_dir.child("a.bin").to_path_buf()));
+    assert_eq!(
+        reports.last().unwrap(),
+        &(total, Some(total), Some(temp_dir.child("e.bin").to_path_buf()))
+    );
+    assert!(reports
+        .iter()
+        .any(|(_, _, path)| *path == Some(temp_dir.child("b/d.bin").to_path_buf())));
+
+    Ok(())
+}
+
+#[test]
+fn interval() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let total = fixture(&temp_dir)?;
+
+    let mut reports = Vec::new();
+    progress::chksum(temp_dir.path(), Duration::from_secs(3600), |progress| {
+        reports.push(progress.processed());
+    })?;
+    assert_eq!(reports, [total]);
+
+    Ok(())
+}
+
+#[test]
+fn missing_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+
+    let result = progress::chksum(temp_dir.child("missing"), Duration::ZERO, |_| {});
+    assert!(matches!(result, Err(ChksumError::Io(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn async_runtime_tokio_directory() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let temp_dir = TempDir::new()?;
+        let total = fixture(&temp_dir)?;
+
+        let mut reports = Vec::new();
+        let digest = progress::async_chksum(temp_dir.path(), Duration::ZERO, |progress| {
+            reports.push((progress.processed(), progress.total()));
+        })
+        .await?;
+        assert_eq!(digest, sha1::chksum(temp_dir.path())?);
+        assert!(reports.len() > 4);
+        assert_eq!(reports.last().unwrap(), &(total, Some(total)));
+    }
+
+    Ok(())
+}
+
+#[cfg(any(target_os = "android", target_os = "linux", target_vendor = "apple"))]
+#[tokio::test]
+async fn terminal() -> Result<(), Error> {
+    use rustix::pty::{grantpt, openpt, ptsname, unlockpt, OpenptFlags};
+
+    let controller = openpt(OpenptFlags::RDWR | OpenptFlags::NOCTTY).map_err(IoError::from)?;
+    grantpt(&controller).map_err(IoError::from)?;
+    unlockpt(&controller).map_err(IoError::from)?;
+    let terminal = PathBuf::from(
+        ptsname(&controller, Vec::new())
+            .map_err(IoError::from)?
+            .into_string()
+            .unwrap(),
+    );
+
+    let result = progress::chksum(&terminal, Duration::ZERO, |_| {});
+    assert!(matches!(result, Err(ChksumError::IsTerminal)));
+
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let result = progress::async_chksum(&terminal, Duration::ZERO, |_| {}).await;
+        assert!(matches!(result, Err(ChksumError::IsTerminal)));
+    }
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "std")]
+
 use std::fs::{read_dir, File};
 use std::io::Error as IoError;
 
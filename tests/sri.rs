@@ -0,0 +1,91 @@
+#![cfg(feature = "alloc")]
+
+#[cfg(feature = "std")]
+use std::io::ErrorKind;
+
+#[cfg(feature = "std")]
+use assert_fs::fixture::FixtureError;
+#[cfg(feature = "std")]
+use assert_fs::prelude::{FileWriteStr, PathChild};
+#[cfg(feature = "std")]
+use assert_fs::TempDir;
+#[cfg(feature = "std")]
+use chksum_sha1::Error as ChksumError;
+use chksum_sha1::{self as sha1, sri};
+
+#[cfg(feature = "std")]
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+}
+
+#[test]
+fn integrity() {
+    let digest = sha1::hash("example data");
+    let integrity = sri::integrity(digest);
+    assert_eq!(integrity, "sha1-n8Qq2sMTA9aLRE5hKfE/YJOg4EU=");
+    assert_eq!(sri::parse_integrity(&integrity), Some(digest));
+}
+
+#[test]
+fn parse_integrity() {
+    let digest = sha1::hash("example");
+    let integrities = [
+        "sha1-w0mcJylzCn+AfvuGdqkty2+KP48=",
+        "SHA1-w0mcJylzCn+AfvuGdqkty2+KP48=",
+        "  sha1-w0mcJylzCn+AfvuGdqkty2+KP48=?ct=application/octet-stream\n",
+        "sha512-O7Eu2jwpjbXeJVl/VNkk8uF+eKJq2JU+2CGO5oLwu76QIeLzAJ0VLJEb8fJexoOpAnFBZnZ6+9jlvQ+wEk7Lig== \
+         sha1-w0mcJylzCn+AfvuGdqkty2+KP48=",
+        // the invalid SHA-1 digest is skipped
+        "sha1-w0mcJylzCn+AfvuGdqkty2+KP48 sha1-w0mcJylzCn+AfvuGdqkty2+KP48=",
+    ];
+    for integrity in integrities {
+        assert_eq!(sri::parse_integrity(integrity), Some(digest), "{integrity}");
+    }
+}
+
+#[test]
+fn invalid_integrity() {
+    let integrities = [
+        "",
+        "sha1",
+        "sha1-",
+        "sha1-w0mcJylzCn+AfvuGdqkty2+KP48",
+        "sha1-w0mcJylzCn-AfvuGdqkty2-KP48=",
+        "sha1-UNhY4JhezH9gQYqvDMWrWH9CwlcKiECVqejMrND2VFw=",
+        "sha256-UNhY4JhezH9gQYqvDMWrWH9CwlcKiECVqejMrND2VFw=",
+        "sha1=w0mcJylzCn+AfvuGdqkty2+KP48=",
+    ];
+    for integrity in integrities {
+        assert_eq!(sri::parse_integrity(integrity), None, "{integrity}");
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn verify() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("package.tgz");
+    file.write_str("example")?;
+
+    assert!(sri::verify(file.path(), "sha1-w0mcJylzCn+AfvuGdqkty2+KP48=")?);
+    assert!(!sri::verify(file.path(), "sha1-n8Qq2sMTA9aLRE5hKfE/YJOg4EU=")?);
+    // any of the listed SHA-1 digests may match
+    assert!(sri::verify(
+        file.path(),
+        "sha1-n8Qq2sMTA9aLRE5hKfE/YJOg4EU= sha1-w0mcJylzCn+AfvuGdqkty2+KP48="
+    )?);
+
+    let result = sri::verify(file.path(), "sha256-UNhY4JhezH9gQYqvDMWrWH9CwlcKiECVqejMrND2VFw=");
+    assert!(matches!(result, Err(ChksumError::Io(error)) if error.kind() == ErrorKind::InvalidInput));
+    let result = sri::verify(
+        temp_dir.child("missing.tgz").path(),
+        "sha1-w0mcJylzCn+AfvuGdqkty2+KP48=",
+    );
+    assert!(matches!(result, Err(ChksumError::Io(error)) if error.kind() == ErrorKind::NotFound));
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use chksum_sha1::{StateError, SHA1, STATE_LENGTH_BYTES};
+
+#[test]
+fn known_vectors() {
+    let digest = format!("{:x}", SHA1::hash(b"abc"));
+    assert_eq!(digest, "a9993e364706816aba3e25717850c26c9cd0d89d");
+
+    let digest = format!(
+        "{:x}",
+        SHA1::hash(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")
+    );
+    assert_eq!(digest, "84983e441c3bd26ebaae4aa1f95129e5e54670f1");
+
+    let mut hash = SHA1::new();
+    for _ in 0..1000 {
+        hash.update([b'a'; 1000]);
+    }
+    let digest = format!("{:x}", hash.digest());
+    assert_eq!(digest, "34aa973cd4c4daa4f61eeb2bdbad27316534016f");
+}
+
+#[test]
+fn export_and_import() -> Result<(), StateError> {
+    let data = (0..3).flat_map(|_| 0..=255u8).collect::<Vec<_>>();
+    for split in [0, 1, 55, 56, 63, 64, 65, 128, 700, data.len()] {
+        let (head, tail) = data.split_at(split);
+
+        let mut hash = SHA1::new();
+        hash.update(head);
+        let state = hash.export_state();
+        assert_eq!(state.len(), STATE_LENGTH_BYTES);
+
+        let mut hash = SHA1::import_state(&state)?;
+        assert_eq!(hash.export_state(), state);
+        hash.update(tail);
+        let digest = format!("{:x}", hash.digest());
+        assert_eq!(digest, "ac2a264c8ec1f4232a40854e8239bc3a697ab1d2");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn export_format() {
+    let mut hash = SHA1::new();
+    hash.update(b"abc");
+    let state = hash.export_state();
+
+    assert_eq!(&state[..5], b"sha1\x01");
+    assert_eq!(&state[5..9], [0x67, 0x45, 0x23, 0x01]);
+    assert_eq!(&state[25..33], 3u64.to_be_bytes());
+    assert_eq!(&state[33..36], b"abc");
+    assert!(state[36..].iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn import_invalid_state() {
+    let state = SHA1::new().export_state();
+
+    assert_eq!(
+        SHA1::import_state(&state[1..]),
+        Err(StateError::InvalidLength {
+            value: STATE_LENGTH_BYTES - 1,
+            proper: STATE_LENGTH_BYTES
+        })
+    );
+
+    let mut invalid = state;
+    invalid[0] = b'S';
+    assert_eq!(SHA1::import_state(&invalid), Err(StateError::InvalidMagic));
+
+    let mut invalid = state;
+    invalid[4] = 2;
+    assert_eq!(SHA1::import_state(&invalid), Err(StateError::UnsupportedVersion(2)));
+
+    let mut invalid = state;
+    invalid[33] = 1;
+    assert_eq!(SHA1::import_state(&invalid), Err(StateError::InvalidPadding));
+}
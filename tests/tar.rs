@@ -0,0 +1,89 @@
+#![cfg(feature = "tar")]
+
+use std::io::Error as IoError;
+use std::path::Path;
+
+use chksum_sha1::tar::{self, Kind};
+use chksum_sha1::{self as sha1, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+/// Builds an archive with the given modification time and owner of all entries.
+fn archive(mtime: u64, uid: u64) -> Result<Vec<u8>, IoError> {
+    fn header(kind: ::tar::EntryType, size: u64, mode: u32, mtime: u64, uid: u64) -> ::tar::Header {
+        let mut header = ::tar::Header::new_gnu();
+        header.set_entry_type(kind);
+        header.set_size(size);
+        header.set_mode(mode);
+        header.set_mtime(mtime);
+        header.set_uid(uid);
+        header.set_gid(uid);
+        header
+    }
+
+    let mut builder = ::tar::Builder::new(Vec::new());
+    let mut dir = header(::tar::EntryType::Directory, 0, 0o755, mtime, uid);
+    builder.append_data(&mut dir, "dir/", &b""[..])?;
+    let mut file = header(::tar::EntryType::Regular, 7, 0o644, mtime, uid);
+    builder.append_data(&mut file, "dir/file.txt", &b"example"[..])?;
+    let mut symlink = header(::tar::EntryType::Symlink, 0, 0o777, mtime, uid);
+    builder.append_link(&mut symlink, "dir/link", Path::new("file.txt"))?;
+    builder.into_inner()
+}
+
+#[test]
+fn entries() -> Result<(), Error> {
+    let entries = tar::entries(&archive(0, 0)?[..])?;
+    assert_eq!(entries.len(), 3);
+
+    assert_eq!(entries[0].path(), Path::new("dir"));
+    assert_eq!(entries[0].kind(), Kind::Directory);
+    assert_eq!(entries[0].mode(), 0o755);
+    assert_eq!(entries[0].digest(), sha1::hash(""));
+
+    assert_eq!(entries[1].path(), Path::new("dir/file.txt"));
+    assert_eq!(entries[1].kind(), Kind::File);
+    assert_eq!(entries[1].size(), 7);
+    assert_eq!(entries[1].link(), None);
+    assert_eq!(entries[1].digest(), sha1::hash("example"));
+
+    assert_eq!(entries[2].kind(), Kind::Symlink);
+    assert_eq!(entries[2].link(), Some(Path::new("file.txt")));
+
+    Ok(())
+}
+
+#[test]
+fn canonical_digest() -> Result<(), Error> {
+    let first = tar::entries(&archive(0, 0)?[..])?;
+    let second = tar::entries(&archive(1_700_000_000, 1000)?[..])?;
+    assert_eq!(tar::canonical_digest(&first), tar::canonical_digest(&second));
+    assert_ne!(tar::canonical_digest(&first), tar::canonical_digest(&first[1..]));
+    assert_eq!(tar::canonical_digest(&[]), sha1::hash(""));
+
+    Ok(())
+}
+
+#[test]
+fn invalid_archive() {
+    let data = [0xFFu8; 1024];
+    assert!(tar::entries(&data[..]).is_err());
+}
+
+#[tokio::test]
+async fn async_runtime_tokio_entries() -> Result<(), Error> {
+    #[cfg(feature = "async-runtime-tokio")]
+    {
+        let data = archive(0, 0)?;
+        let entries = tar::async_entries(std::io::Cursor::new(data.clone())).await?;
+        assert_eq!(entries, tar::entries(&data[..])?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,180 @@
+#![cfg(feature = "torrent")]
+
+use std::io::Error as IoError;
+
+use assert_fs::fixture::{ChildPath, FixtureError};
+use assert_fs::prelude::{FileTouch, FileWriteBin, PathChild};
+use assert_fs::TempDir;
+use chksum_sha1::torrent::{self, Files, Info};
+use chksum_sha1::Error as ChksumError;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+fn fixture(temp_dir: &TempDir) -> Result<ChildPath, Error> {
+    let dir = temp_dir.child("dir");
+    let file = dir.child("a.txt");
+    file.touch()?;
+    file.write_binary(b"hello world")?;
+    let file = dir.child("sub").child("b.txt");
+    file.touch()?;
+    file.write_binary(&[b'x'; 100])?;
+    Ok(dir)
+}
+
+fn torrent(info: &Info) -> Vec<u8> {
+    let mut torrent = b"d4:info".to_vec();
+    torrent.extend(info.to_bencode());
+    torrent.push(b'e');
+    torrent
+}
+
+#[test]
+fn single_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let dir = fixture(&temp_dir)?;
+    let file = dir.child("sub").child("b.txt");
+
+    let info = torrent::info(file.path(), 64)?;
+    assert_eq!(info.name(), "b.txt");
+    assert_eq!(info.files(), &Files::Single { length: 100 });
+    assert_eq!(info.pieces().len(), 2);
+    assert_eq!(&info.to_bencode()[..40], b"d6:lengthi100e4:name5:b.txt12:piece leng");
+    assert_eq!(
+        info.info_hash().to_hex_lowercase(),
+        "830754631149feb7437203cf7cf3d66d50eef81d"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn directory() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let dir = fixture(&temp_dir)?;
+
+    let info = torrent::info(dir.path(), 64)?;
+    assert_eq!(info.name(), "dir");
+    assert_eq!(info.length(), 111);
+    let Files::Multiple(files) = info.files() else {
+        panic!("expected multiple files");
+    };
+    assert_eq!(files[0].path(), ["a.txt"]);
+    assert_eq!(files[1].path(), ["sub", "b.txt"]);
+    let pieces = info
+        .pieces()
+        .iter()
+        .map(|digest| digest.to_hex_lowercase())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pieces,
+        [
+            "8a79c66dfc008bdf8dffbeca5792f7ac9c12f1fe",
+            "f89d4936f190d205f17b588e0d61dc9e085fade6"
+        ]
+    );
+    assert_eq!(info.pieces_bytes().len(), 40);
+    assert_eq!(
+        info.info_hash().to_hex_lowercase(),
+        "e21d53ade44e22cc9ddbc94dba969761604c7f78"
+    );
+
+    let torrent = torrent(&info);
+    assert_eq!(
+        torrent::info_hash(&torrent)?.to_hex_lowercase(),
+        info.info_hash().to_hex_lowercase()
+    );
+    assert_eq!(Info::from_torrent(&torrent)?, info);
+
+    Ok(())
+}
+
+#[test]
+fn verify() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let dir = fixture(&temp_dir)?;
+    let torrent = torrent(&torrent::info(dir.path(), 64)?);
+
+    let verification = torrent::verify(&torrent, dir.path())?;
+    assert!(verification.is_valid());
+    assert_eq!(verification.pieces(), [true, true]);
+
+    dir.child("sub").child("b.txt").write_binary(&[b'y'; 100])?;
+    let verification = torrent::verify(&torrent, dir.path())?;
+    assert!(!verification.is_valid());
+    assert_eq!(verification.invalid_pieces().collect::<Vec<_>>(), [0, 1]);
+
+    dir.child("sub").child("b.txt").write_binary(&[b'x'; 53])?;
+    let verification = torrent::verify(&torrent, dir.path())?;
+    assert_eq!(verification.pieces(), [true, false]);
+
+    std::fs::remove_file(dir.child("a.txt").path())?;
+    let verification = torrent::verify(&torrent, dir.path())?;
+    assert_eq!(verification.pieces(), [false, false]);
+
+    Ok(())
+}
+
+#[test]
+fn verify_missing_large_file() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let dir = temp_dir.child("dir");
+    dir.child("a.txt").write_binary(&[b'x'; 100])?;
+    dir.child("c.txt").write_binary(&[b'z'; 100])?;
+
+    // the missing file of almost 1 TiB must be skipped, not hashed
+    let piece_length: u64 = 1 << 30;
+    let length: u64 = (1 << 40) - 100;
+    let pieces = (200 + length).div_ceil(piece_length) as usize;
+    let mut torrent = format!(
+        "d4:infod5:filesld6:lengthi100e4:pathl5:a.txteed6:lengthi{length}e4:pathl5:b.txteed6:lengthi100e4:pathl5:c.\
+         txteee4:name3:dir12:piece lengthi{piece_length}e6:pieces{}:",
+        pieces * 20
+    )
+    .into_bytes();
+    torrent.extend(std::iter::repeat(0).take((pieces - 1) * 20));
+    // the last piece starts at the piece boundary right after the missing file
+    torrent.extend(chksum_sha1::hash([b'z'; 100]).as_bytes());
+    torrent.extend(b"ee");
+
+    let verification = torrent::verify(&torrent, dir.path())?;
+    assert_eq!(verification.pieces().len(), pieces);
+    assert_eq!(verification.invalid_pieces().count(), pieces - 1);
+    assert_eq!(verification.pieces().last(), Some(&true));
+
+    Ok(())
+}
+
+#[test]
+fn invalid_torrent() {
+    assert!(torrent::info_hash(b"d4:infoi1e").is_err());
+    assert!(Info::from_torrent(b"d4:infod4:name2:..12:piece lengthi64e6:pieces0:6:lengthi0eee").is_err());
+    assert!(Info::from_torrent(b"d4:infod4:name1:a12:piece lengthi64e6:pieces0:6:lengthi1eee").is_err());
+    assert!(Info::from_torrent(b"d4:infod4:name1:a12:piece lengthi64e6:pieces0:6:lengthi0eee").is_ok());
+}
+
+#[test]
+fn overflowing_length() {
+    let half = u64::MAX / 2 + 1;
+    let torrent = format!(
+        "d4:infod5:filesld6:lengthi{half}e4:pathl1:aeed6:lengthi{half}e4:pathl1:beee4:name1:a12:piece \
+         lengthi64e6:pieces0:ee"
+    );
+    assert!(Info::from_torrent(torrent.as_bytes()).is_err());
+
+    // the largest lengths of bencode integers
+    let max = i64::MAX;
+    let torrent = format!(
+        "d4:infod5:filesld6:lengthi{max}e4:pathl1:aeed6:lengthi{max}e4:pathl1:beed6:lengthi{max}e4:pathl1:ceee4:name1:\
+         a12:piece lengthi64e6:pieces0:ee"
+    );
+    let error = Info::from_torrent(torrent.as_bytes()).unwrap_err();
+    assert!(error.to_string().contains("overflow"), "{error}");
+}
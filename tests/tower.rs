@@ -0,0 +1,143 @@
+#![cfg(feature = "tower")]
+
+use std::convert::Infallible;
+use std::io::Error as IoError;
+
+use bytes::Bytes;
+use chksum_sha1::tower::{BodyDigest, Error as BodyError, HashBody, HashLayer};
+use chksum_sha1::{self as sha1, Digest};
+use http::{HeaderName, Request, Response, StatusCode};
+use http_body_util::{BodyExt, Full};
+use hyper::body::Incoming;
+use hyper_util::rt::TokioIo;
+use hyper_util::service::TowerToHyperService;
+use tokio::net::{TcpListener, TcpStream};
+use tower::{service_fn, Layer, ServiceExt};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Body(#[from] BodyError),
+    #[error(transparent)]
+    Http(#[from] http::Error),
+    #[error(transparent)]
+    Hyper(#[from] hyper::Error),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+const HEADER: HeaderName = HeaderName::from_static("x-content-sha1");
+
+/// Responds with the hexadecimal digest of the request body, or rejects the request if the body fails.
+async fn upload(request: Request<HashBody<Incoming>>) -> Result<Response<Full<Bytes>>, Infallible> {
+    let digest = request.extensions().get::<BodyDigest>().cloned().unwrap();
+    assert_eq!(digest.get(), None);
+    let response = match request.into_body().collect().await {
+        Ok(_) => Response::new(Full::from(digest.get().unwrap().to_hex_lowercase())),
+        Err(error) => {
+            let mut response = Response::new(Full::from(error.to_string()));
+            *response.status_mut() = StatusCode::BAD_REQUEST;
+            response
+        },
+    };
+    Ok(response)
+}
+
+/// Starts a server on a local port, returning its address.
+async fn serve() -> Result<String, Error> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?.to_string();
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let service = HashLayer::verify(HEADER).layer(service_fn(upload));
+            tokio::spawn(
+                hyper::server::conn::http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), TowerToHyperService::new(service)),
+            );
+        }
+    });
+    Ok(address)
+}
+
+/// Sends the body with the optional header to the server, returning the status and the body of the response.
+async fn send(address: &str, body: &'static str, header: Option<&str>) -> Result<(StatusCode, String), Error> {
+    let stream = TcpStream::connect(address).await?;
+    let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream)).await?;
+    tokio::spawn(connection);
+
+    let mut request = Request::post("/upload").header(http::header::HOST, address);
+    if let Some(header) = header {
+        request = request.header(HEADER, header);
+    }
+    let response = sender.send_request(request.body(Full::new(Bytes::from(body)))?).await?;
+    let status = response.status();
+    let body = response.into_body().collect().await?.to_bytes();
+    Ok((status, String::from_utf8_lossy(&body).into_owned()))
+}
+
+#[tokio::test]
+async fn verify() -> Result<(), Error> {
+    let address = serve().await?;
+    let digest = sha1::hash("example data").to_hex_lowercase();
+
+    let (status, body) = send(&address, "example data", Some(&digest)).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, digest);
+
+    let (status, body) = send(&address, "example data", Some(&digest.to_uppercase())).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, digest);
+
+    let (status, body) = send(&address, "corrupted data", Some(&digest)).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.contains("doesn't match"));
+
+    let (status, body) = send(&address, "example data", Some("invalid")).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body, "Invalid digest header");
+
+    let (status, body) = send(&address, "unverified data", None).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, sha1::hash("unverified data").to_hex_lowercase());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn response() -> Result<(), Error> {
+    let service = HashLayer::new().layer(service_fn(|request: Request<HashBody<Full<Bytes>>>| {
+        async move {
+            let body = request.into_body().collect().await?.to_bytes();
+            Ok::<_, BodyError>(Response::new(Full::new(body)))
+        }
+    }));
+    let request = Request::new(Full::new(Bytes::from("example data")));
+    let response = service.oneshot(request).await?;
+
+    let digest = response.extensions().get::<BodyDigest>().cloned().unwrap();
+    assert_eq!(digest.get(), None);
+    let body = response.into_body().collect().await?.to_bytes();
+    assert_eq!(body, "example data");
+    assert_eq!(digest.get(), Some(sha1::hash("example data")));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn body() -> Result<(), Error> {
+    let body = HashBody::new(Full::new(Bytes::new()));
+    let digest = body.digest();
+    body.collect().await?;
+    assert_eq!(digest.get(), Some(sha1::hash("")));
+
+    let expected = Digest::new([0; 20]);
+    let body = HashBody::verify(Full::new(Bytes::from("example data")), expected);
+    let error = body.collect().await.unwrap_err();
+    assert!(matches!(
+        error,
+        BodyError::Mismatch { expected: e, computed: c } if e == expected && c == sha1::hash("example data")
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use chksum_sha1 as sha1;
+use chksum_sha1::uuid::{Uuid, UUID_LENGTH_BYTES};
+
+#[test]
+fn namespaces() {
+    // the values from RFC 9562, Section 6.6
+    let namespaces = [
+        (Uuid::NAMESPACE_DNS, "6ba7b810-9dad-11d1-80b4-00c04fd430c8"),
+        (Uuid::NAMESPACE_URL, "6ba7b811-9dad-11d1-80b4-00c04fd430c8"),
+        (Uuid::NAMESPACE_OID, "6ba7b812-9dad-11d1-80b4-00c04fd430c8"),
+        (Uuid::NAMESPACE_X500, "6ba7b814-9dad-11d1-80b4-00c04fd430c8"),
+    ];
+    for (namespace, expected) in namespaces {
+        assert_eq!(namespace.version(), 1);
+        assert_eq!(format!("{namespace}"), expected);
+    }
+}
+
+#[test]
+fn uuid_v5() {
+    // the example from RFC 9562, Appendix A.4
+    let uuid = sha1::uuid_v5(Uuid::NAMESPACE_DNS, "www.example.com");
+    assert_eq!(format!("{uuid}"), "2ed6657d-e927-568b-95e1-2665a8aea6a2");
+
+    let uuids = [
+        (
+            Uuid::NAMESPACE_DNS,
+            "python.org",
+            "886313e1-3b8a-5372-9b90-0c9aee199e5d",
+        ),
+        (
+            Uuid::NAMESPACE_URL,
+            "http://python.org/",
+            "4c565f0d-3f5a-5890-b41b-20cf47701c5e",
+        ),
+        (Uuid::NAMESPACE_OID, "1.3.6.1", "1447fa61-5277-5fef-a9b3-fbc6e44f4af3"),
+        (Uuid::NAMESPACE_X500, "c=ca", "cc957dd1-a972-5349-98cd-874190002798"),
+    ];
+    for (namespace, name, expected) in uuids {
+        let uuid = sha1::uuid_v5(namespace, name);
+        assert_eq!(format!("{uuid}"), expected);
+        assert_eq!(uuid.version(), 5);
+        assert_eq!(uuid.as_bytes()[8] >> 6, 0b10);
+    }
+}
+
+#[test]
+fn bytes() {
+    let bytes = [0xAB; UUID_LENGTH_BYTES];
+    let uuid = Uuid::from(bytes);
+    assert_eq!(uuid, Uuid::new(bytes));
+    assert_eq!(uuid.as_bytes(), bytes);
+    assert_eq!(uuid.into_inner(), bytes);
+    assert_eq!(format!("{uuid}"), "abababab-abab-abab-abab-abababababab");
+    assert_eq!(format!("{uuid:>40}"), "    abababab-abab-abab-abab-abababababab");
+    assert_eq!(format!("{uuid:*<38}"), "abababab-abab-abab-abab-abababababab**");
+    assert_eq!(format!("{uuid:.8}"), "abababab");
+    assert_ne!(sha1::uuid_v5(uuid, "name"), sha1::uuid_v5(uuid, "other name"));
+}
@@ -0,0 +1,138 @@
+#![cfg(all(
+    feature = "xattr",
+    any(target_os = "android", target_os = "linux", target_vendor = "apple")
+))]
+
+use std::fs;
+use std::io::{Error as IoError, ErrorKind};
+use std::path::Path;
+
+use assert_fs::fixture::FixtureError;
+use assert_fs::prelude::{FileWriteStr, PathChild};
+use assert_fs::TempDir;
+use chksum_sha1::xattr::{self, Status, DIGEST_ATTRIBUTE, MODIFIED_ATTRIBUTE};
+use chksum_sha1::{self as sha1, Error as ChksumError};
+use rustix::fs::{setxattr, XattrFlags};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Fixture(#[from] FixtureError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+fn set(path: &Path, name: &str, value: &str) -> Result<(), Error> {
+    setxattr(path, name, value.as_bytes(), XattrFlags::empty()).map_err(IoError::from)?;
+    Ok(())
+}
+
+#[test]
+fn store_and_verify() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_str("example data")?;
+
+    let digest = xattr::store(file.path())?;
+    assert_eq!(digest, sha1::hash("example data"));
+
+    let attributes = xattr::read(file.path())?.unwrap();
+    assert_eq!(attributes.digest(), digest);
+    assert_eq!(attributes.modified(), Some(fs::metadata(file.path())?.modified()?));
+
+    assert_eq!(xattr::verify(file.path())?, Status::Valid);
+
+    Ok(())
+}
+
+#[test]
+fn missing() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_str("example data")?;
+
+    assert_eq!(xattr::read(file.path())?, None);
+    assert_eq!(xattr::verify(file.path())?, Status::Missing);
+
+    Ok(())
+}
+
+#[test]
+fn stale() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_str("example data")?;
+
+    xattr::store(file.path())?;
+    set(file.path(), MODIFIED_ATTRIBUTE, "0")?;
+    assert_eq!(xattr::verify(file.path())?, Status::Stale);
+
+    // a digest stored without the modification time cannot be trusted either
+    let file = temp_dir.child("other.txt");
+    file.write_str("example data")?;
+    set(
+        file.path(),
+        DIGEST_ATTRIBUTE,
+        "9fc42adac31303d68b444e6129f13f6093a0e045",
+    )?;
+    assert_eq!(xattr::read(file.path())?.unwrap().modified(), None);
+    assert_eq!(xattr::verify(file.path())?, Status::Stale);
+
+    Ok(())
+}
+
+#[test]
+fn mismatch() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_str("example data")?;
+
+    xattr::store(file.path())?;
+    set(
+        file.path(),
+        DIGEST_ATTRIBUTE,
+        "0123456789abcdef0123456789abcdef01234567",
+    )?;
+    let Status::Mismatch { stored, computed } = xattr::verify(file.path())? else {
+        panic!("digest must not match");
+    };
+    assert_eq!(stored.to_hex_lowercase(), "0123456789abcdef0123456789abcdef01234567");
+    assert_eq!(computed, sha1::hash("example data"));
+
+    Ok(())
+}
+
+#[test]
+fn invalid() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_str("example data")?;
+
+    set(file.path(), DIGEST_ATTRIBUTE, "not a digest")?;
+    assert!(matches!(xattr::read(file.path()), Err(ChksumError::Io(_))));
+    assert!(matches!(xattr::verify(file.path()), Err(ChksumError::Io(_))));
+
+    Ok(())
+}
+
+#[test]
+fn too_long() -> Result<(), Error> {
+    let temp_dir = TempDir::new()?;
+    let file = temp_dir.child("file.txt");
+    file.write_str("example data")?;
+
+    let is_invalid_data =
+        |result| matches!(result, Err(ChksumError::Io(error)) if error.kind() == ErrorKind::InvalidData);
+
+    set(file.path(), DIGEST_ATTRIBUTE, &"0".repeat(100))?;
+    assert!(is_invalid_data(xattr::read(file.path()).map(drop)));
+
+    xattr::store(file.path())?;
+    set(file.path(), MODIFIED_ATTRIBUTE, &"1".repeat(100))?;
+    assert!(is_invalid_data(xattr::read(file.path()).map(drop)));
+    assert!(is_invalid_data(xattr::verify(file.path()).map(drop)));
+
+    Ok(())
+}
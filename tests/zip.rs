@@ -0,0 +1,155 @@
+#![cfg(feature = "zip")]
+
+use std::io::{Cursor, Error as IoError, ErrorKind, Write};
+
+use ::zip::result::ZipError;
+use ::zip::write::SimpleFileOptions;
+use ::zip::{CompressionMethod, ZipWriter};
+use chksum_sha1::zip::{self, Manifest, Mismatch, MANIFEST_NAME};
+use chksum_sha1::{self as sha1, Error as ChksumError};
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    Chksum(#[from] ChksumError),
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[error(transparent)]
+    Zip(#[from] ZipError),
+}
+
+/// Builds an archive with the given files, compressing every other file.
+fn archive(files: &[(&str, &[u8])]) -> Result<Vec<u8>, Error> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for (index, (name, data)) in files.iter().enumerate() {
+        let method = if index % 2 == 0 {
+            CompressionMethod::Deflated
+        } else {
+            CompressionMethod::Stored
+        };
+        if let Some(name) = name.strip_suffix('/') {
+            writer.add_directory(name, SimpleFileOptions::default())?;
+        } else {
+            writer.start_file(*name, SimpleFileOptions::default().compression_method(method))?;
+            writer.write_all(data)?;
+        }
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+#[test]
+fn entries() -> Result<(), Error> {
+    let data = "example data".repeat(100);
+    let archive = archive(&[("a/", b""), ("a/b.txt", data.as_bytes()), ("c.txt", b"example")])?;
+
+    let entries = zip::entries(Cursor::new(archive))?;
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].name(), "a/");
+    assert!(entries[0].is_dir());
+    assert_eq!(entries[1].name(), "a/b.txt");
+    assert_eq!(entries[1].size(), data.len() as u64);
+    assert_eq!(entries[1].digest(), sha1::hash(&data));
+    assert_eq!(entries[2].digest(), sha1::hash("example"));
+
+    Ok(())
+}
+
+#[test]
+fn invalid_archive() {
+    let error = zip::entries(Cursor::new(b"not a zip archive")).unwrap_err();
+    assert!(matches!(error, ChksumError::Io(error) if error.kind() == ErrorKind::InvalidData));
+}
+
+#[test]
+fn manifest() -> Result<(), Error> {
+    let name = format!("{}/Example.class", "very/long/package/name".repeat(4));
+    let archive = archive(&[
+        ("META-INF/", b""),
+        ("META-INF/SIGNER.SF", b"signature"),
+        ("META-INF/SIGNER.RSA", b"signature"),
+        (&name, b"example"),
+        ("data.txt", b"data"),
+    ])?;
+    let entries = zip::entries(Cursor::new(archive))?;
+
+    let manifest = Manifest::new(&entries);
+    assert_eq!(manifest.len(), 2);
+    assert_eq!(manifest.get(&name), Some(sha1::hash("example")));
+    assert_eq!(manifest.get("META-INF/SIGNER.SF"), None);
+
+    let text = manifest.to_string();
+    assert!(text.starts_with("Manifest-Version: 1.0\r\n"));
+    assert!(text.contains("Name: data.txt\r\nSHA1-Digest: oXyaqmHoChv3HQ2FCvTluqmAC70=\r\n"));
+    assert!(text.split("\r\n").all(|line| line.len() <= 72));
+    assert_eq!(Manifest::parse(text.as_bytes())?, manifest);
+
+    Ok(())
+}
+
+#[test]
+fn parse_manifest() -> Result<(), Error> {
+    let text = b"Manifest-Version: 1.0\nCreated-By: 17 (Oracle)\n\nName: a.txt\nSHA-256-Digest: ignored\n\nName: long\n /name.txt\nsha1-digest: w0mcJylzCn+AfvuGdqkty2+KP48=\n";
+    let manifest = Manifest::parse(text)?;
+    assert_eq!(
+        manifest.iter().collect::<Vec<_>>(),
+        [("long/name.txt", sha1::hash("example"))]
+    );
+
+    for text in [
+        &b"Manifest-Version: 1.0\r\n\r\nSHA1-Digest: w0mcJylzCn+AfvuGdqkty2+KP48=\r\n"[..],
+        b"Manifest-Version: 1.0\r\n\r\nName: a.txt\r\nSHA1-Digest: AAAA\r\n",
+        b"Manifest-Version: 1.0\r\n\r\nName a.txt\r\n",
+        b" continuation\r\n",
+    ] {
+        let error = Manifest::parse(text).unwrap_err();
+        assert!(matches!(error, ChksumError::Io(error) if error.kind() == ErrorKind::InvalidData));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn verify() -> Result<(), Error> {
+    let original = archive(&[("a.txt", b"example"), ("b.txt", b"data")])?;
+    let manifest = Manifest::new(&zip::entries(Cursor::new(original))?).to_string();
+
+    let valid = archive(&[
+        (MANIFEST_NAME, manifest.as_bytes()),
+        ("a.txt", b"example"),
+        ("b.txt", b"data"),
+    ])?;
+    assert!(zip::verify(Cursor::new(valid))?.is_valid());
+
+    let modified = archive(&[
+        (MANIFEST_NAME, manifest.as_bytes()),
+        ("a.txt", b"modified"),
+        ("c.txt", b"extra"),
+    ])?;
+    let verification = zip::verify(Cursor::new(modified))?;
+    assert_eq!(
+        verification.mismatches(),
+        [
+            Mismatch::Modified {
+                name: "a.txt".to_owned(),
+                expected: sha1::hash("example"),
+                computed: sha1::hash("modified"),
+            },
+            Mismatch::Missing {
+                name: "b.txt".to_owned()
+            },
+            Mismatch::Unlisted {
+                name: "c.txt".to_owned()
+            },
+        ]
+    );
+    assert_eq!(
+        verification.mismatches()[1].to_string(),
+        "`b.txt` is missing from the archive"
+    );
+
+    let unsigned = archive(&[("a.txt", b"example")])?;
+    let error = zip::verify(Cursor::new(unsigned)).unwrap_err();
+    assert!(matches!(error, ChksumError::Io(error) if error.kind() == ErrorKind::NotFound));
+
+    Ok(())
+}
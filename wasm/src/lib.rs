@@ -0,0 +1,96 @@
+//! WebAssembly bindings for the [`chksum-sha1`](https://docs.rs/chksum-sha1) crate.
+//!
+//! The bindings expose the in-memory hashing of the crate to JavaScript through [`wasm-bindgen`](https://docs.rs/wasm-bindgen), e.g. to hash files selected by the user in the browser before upload, with the same digest as computed by the server.
+//!
+//! # Building
+//!
+//! ```shell
+//! cargo build --release --target wasm32-unknown-unknown --package chksum-sha1-wasm
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/chksum_sha1_wasm.wasm
+//! ```
+//!
+//! # Testing
+//!
+//! The tests run in Node.js with the `wasm-bindgen-test-runner` of the `wasm-bindgen-cli` crate, which is configured as the runner of the `wasm32-unknown-unknown` target.
+//!
+//! ```shell
+//! cargo test --target wasm32-unknown-unknown --package chksum-sha1-wasm
+//! ```
+//!
+//! # Example
+//!
+//! ```js
+//! import init, { Sha1 } from "./pkg/chksum_sha1_wasm.js";
+//!
+//! await init();
+//!
+//! async function digest(file) {
+//!     const hash = new Sha1();
+//!     try {
+//!         for await (const chunk of file.stream()) {
+//!             hash.update(chunk);
+//!         }
+//!         return hash.digestHex();
+//!     } finally {
+//!         hash.free();
+//!     }
+//! }
+//! ```
+
+use chksum_sha1::SHA1;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// An incremental SHA-1 hash, the `Sha1` class in JavaScript.
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default)]
+pub struct Sha1 {
+    inner: SHA1,
+}
+
+#[wasm_bindgen]
+impl Sha1 {
+    /// Creates a new hash.
+    #[must_use]
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        let inner = SHA1::new();
+        Self { inner }
+    }
+
+    /// Updates the hash with the chunk of data, given as a `Uint8Array`.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Resets the hash to its initial state.
+    pub fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    /// Returns the digest of the data hashed so far as a `Uint8Array`, the hash may be updated further.
+    #[must_use]
+    pub fn digest(&self) -> Vec<u8> {
+        self.inner.digest().as_bytes().to_vec()
+    }
+
+    /// Returns the digest of the data hashed so far as a lowercase hexadecimal string.
+    #[must_use]
+    #[wasm_bindgen(js_name = digestHex)]
+    pub fn digest_hex(&self) -> String {
+        self.inner.digest().to_hex_lowercase()
+    }
+}
+
+/// Returns the digest of the data, given as a `Uint8Array`.
+#[must_use]
+#[wasm_bindgen]
+pub fn hash(data: &[u8]) -> Vec<u8> {
+    chksum_sha1::hash(data).as_bytes().to_vec()
+}
+
+/// Returns the digest of the data, given as a `Uint8Array`, as a lowercase hexadecimal string.
+#[must_use]
+#[wasm_bindgen(js_name = hashHex)]
+pub fn hash_hex(data: &[u8]) -> String {
+    chksum_sha1::hash(data).to_hex_lowercase()
+}
@@ -0,0 +1,37 @@
+#![cfg(target_arch = "wasm32")]
+
+use chksum_sha1_wasm::{hash, hash_hex, Sha1};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const EXAMPLE_DATA: &str = "9fc42adac31303d68b444e6129f13f6093a0e045";
+const EMPTY: &str = "da39a3ee5e6b4b0d3255bfef95601890afd80709";
+
+#[wasm_bindgen_test]
+fn one_shot() {
+    assert_eq!(hash_hex(b"example data"), EXAMPLE_DATA);
+    assert_eq!(hash_hex(b""), EMPTY);
+    assert_eq!(hash(b"example data"), chksum_sha1::hash("example data").as_bytes());
+}
+
+#[wasm_bindgen_test]
+fn incremental() {
+    let mut hash = Sha1::new();
+    assert_eq!(hash.digest_hex(), EMPTY);
+    hash.update(b"example");
+    hash.update(b" ");
+    hash.update(b"data");
+    assert_eq!(hash.digest_hex(), EXAMPLE_DATA);
+    assert_eq!(hash.digest(), chksum_sha1::hash("example data").as_bytes());
+    hash.reset();
+    assert_eq!(hash.digest_hex(), EMPTY);
+}
+
+#[wasm_bindgen_test]
+fn chunks() {
+    let data: Vec<u8> = (0..100_000).map(|index| (index % 251) as u8).collect();
+    let mut hash = Sha1::new();
+    for chunk in data.chunks(64 * 1024 - 1) {
+        hash.update(chunk);
+    }
+    assert_eq!(hash.digest(), chksum_sha1::hash(&data).as_bytes());
+}